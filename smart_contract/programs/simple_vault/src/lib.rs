@@ -7,41 +7,200 @@
  * 主な機能:
  * 1. トークンの預け入れと引き出し
  *    - ユーザーは自分のトークンを金庫に預け入れ、必要に応じて引き出すことができます
+ *    - 初期化時のトークンに加えて、ミントを登録することで複数のSPLトークンを1つの金庫で保管できます
+ *    - SPLトークンとToken-2022の両方に対応し、転送手数料付きのミントは実際に受け取った量を記録します
  * 
  * 2. タイムロック
  *    - 指定した期間、金庫からの引き出しをロックする機能
+ *    - 引き出しを予約し、待機時間の経過後にのみ実行できるようにする機能
+ *    - 延長のみ可能なモードでは、鍵が漏洩してもロックを短縮・解除できません
  *    - 不正アクセスからの保護や、長期保管のセキュリティ向上に役立ちます
  * 
  * 3. 権限委任
  *    - 金庫の所有者が他のアドレスに操作権限を委任できる機能
+ *    - 委任者ごとに引き出し枠、有効期限、許可する操作を指定できます
  *    - チーム運用や緊急時のバックアップアクセスに有用です
  * 
  * 4. 多重署名（マルチシグ）
  *    - 複数の署名者が承認した場合のみ引き出しを許可する機能
+ *    - 引き出し金額に応じて必要な署名数を変えることができます
  *    - 大きな資金移動に対する追加のセキュリティレイヤーを提供します
  * 
  * 5. 引き出し制限
  *    - 1回の取引で引き出せる最大金額を制限する機能
+ *    - 一定期間内に引き出せる合計金額を制限する機能
+ *    - 引き出しの送金先を許可リストに登録されたアドレスに限定する機能
  *    - 不正アクセスがあった場合のリスク軽減に役立ちます
  * 
  * 6. 所有権譲渡
  *    - 金庫の所有権を別のアドレスに安全に譲渡する機能
  *    - 二段階の承認プロセスにより誤送信のリスクを軽減します
  * 
+ * 7. 金庫の閉鎖
+ *    - 残高を指定した送金先に移動し、金庫とトークンアカウントを閉鎖する機能
+ *    - アカウントに預けられたレントを所有者に返却します
+ * 
+ * 8. 権利確定（ベスティング）
+ *    - 受益者ごとにクリフ付きの線形な権利確定スケジュールを作成する機能
+ *    - 確定した分は受益者がいつでも受け取れ、取り消し可能なスケジュールは未確定分を取り消せます
+ * 
+ * 9. 定期支払い
+ *    - 一定期間ごとに一定額を受取人に支払う機能（給与支払いなど）
+ *    - 支払いは誰でも実行でき、タイムロックや引き出し制限に従います
+ * 
+ * 10. 緊急停止
+ *    - ガーディアンが資金の移動を即時に停止できる機能
+ *    - 停止の解除は所有者（多重署名の場合は必要な承認）が必要で、ガーディアンは資金を動かせません
+ * 
+ * 11. ソーシャルリカバリー
+ *    - 所有者の鍵を紛失した場合に、M-of-Nのガーディアンが新しい所有者を指定できる機能
+ *    - 待機時間中は現在の所有者がリカバリーを拒否できます
+ * 
+ * 12. 相続（デッドマンスイッチ）
+ *    - 所有者が一定期間操作を行わなかった場合に、受益者が所有権または残高を請求できる機能
+ *    - 所有者の署名する操作やheartbeat命令でタイマーがリセットされます
+ * 
  * アーキテクチャ:
  * - このコントラクトはAnchorフレームワークを使用して実装されています
- * - 金庫アカウントはPDA（Program Derived Address）として作成され、所有者のアドレスから派生します
+ * - 金庫アカウントはPDA（Program Derived Address）として作成され、作成者のアドレスと金庫IDから派生します
+ * - 作成者は不変のため、所有権を譲渡しても金庫のアドレスと署名用のシードは変わりません
+ * - 1つのウォレットが金庫IDごとに複数の独立した金庫を持つことができます
  * - 各金庫には関連するSPLトークンアカウントがあり、金庫PDAが権限を持ちます
- * - マルチシグ機能は保留中トランザクションのリストを内部で管理し、必要な署名が集まると実行されます
+ * - トークンの操作はtoken_interfaceを通じて行い、転送はミントの小数点以下の桁数を確認するtransfer_checkedを使用します
+ * - 転送フックや永久委任者など、金庫の資金を金庫の外から動かせるToken-2022の拡張機能を持つミントは使用できません
+ * - 追加で登録したミントのトークンアカウントは、金庫とミントから派生するPDAとして作成され、
+ *   金庫のミント登録簿で管理されます
+ * - マルチシグ機能の保留中トランザクションは、金庫と連番から派生する個別のPDAに保存され、
+ *   必要な署名が集まった後に誰でも実行（クランク）できます
+ * - 実行済み・キャンセル済みの保留中トランザクションは閉鎖でき、レントは提案者に返却されます
+ * - 引き出し予約も金庫と連番から派生する個別のPDAに保存され、実行またはキャンセル時に閉鎖されます
+ * - 権利確定スケジュールと定期支払いも同様に、金庫と連番から派生する個別のPDAに保存されます
+ * - 主な状態変更ではAnchorイベントを発行し、インデクサーがアカウントの差分を取らずに履歴を追跡できます
+ * - 監査ログを作成した金庫では、資金の移動、設定変更、所有権の変更が金庫から派生する
+ *   固定サイズのリングバッファPDAにも記録され、RPCのログ保持期間に依存せずに直近の履歴を確認できます
  */
 
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use std::collections::HashSet;
 
 declare_id!("GGCcGkcUoT1oCbPxkHrxpHDkLDrb9TYN8Hx2ffAEYLaQ");
 
+/**
+ * 初期化時に確保する容量
+ * 
+ * 金庫アカウントはこの容量で作成され、resize_vault命令で後から変更できます。
+ */
+pub const DEFAULT_MAX_DELEGATES: u16 = 10;  // 委任者の最大数
+pub const DEFAULT_MAX_SIGNERS: u16 = 5;     // 追加署名者の最大数
+pub const DEFAULT_MAX_ALLOWLIST: u16 = 10;  // 許可リストの最大登録数
+
+/**
+ * 金額別の承認段階の最大数
+ */
+pub const MAX_APPROVAL_TIERS: usize = 5;
+
+/**
+ * 初期化時のトークン以外に登録できるミントの最大数
+ */
+pub const MAX_MINTS: usize = 8;
+
+/**
+ * 期間ごとの引き出し枠を記録する区間の数
+ * 
+ * 期間を区間に分けて引き出し額を記録し、直近の期間に含まれる区間の合計で判定します。
+ */
+pub const WINDOW_BUCKETS: usize = 8;
+
+/**
+ * ガーディアンの最大数
+ */
+pub const MAX_GUARDIANS: usize = 5;
+
+/**
+ * ガーディアンによるリカバリーの待機時間（秒）の初期値
+ * 
+ * 必要な承認が集まってからこの期間が経過するまで、所有者はリカバリーを拒否できます。
+ */
+pub const DEFAULT_RECOVERY_DELAY: u64 = 3 * 24 * 60 * 60; // 3日間
+
+/**
+ * 保留中トランザクションの有効期間（秒）の初期値
+ * 
+ * この期間を過ぎた保留中トランザクションは承認・実行できなくなります。
+ * set_transaction_ttl命令で変更でき、0を指定すると期限なしになります。
+ */
+pub const DEFAULT_TRANSACTION_TTL: u64 = 7 * 24 * 60 * 60; // 7日間
+
+/**
+ * 許可リストに追加した送金先が有効になるまでの待機時間（秒）の初期値
+ * 
+ * 鍵が漏洩した場合でも、攻撃者が自分のアドレスを追加してすぐに引き出すことを防ぎます。
+ */
+pub const DEFAULT_ALLOWLIST_DELAY: u64 = 24 * 60 * 60; // 24時間
+
+/**
+ * 委任者の権限フラグ
+ * 
+ * 委任者ごとに許可する操作をビットの組み合わせで指定します。
+ */
+pub const DELEGATE_PERMISSION_WITHDRAW: u8 = 1 << 0; // 引き出し
+pub const DELEGATE_PERMISSION_DEPOSIT: u8 = 1 << 1;  // 預け入れ
+pub const DELEGATE_PERMISSION_ALL: u8 = DELEGATE_PERMISSION_WITHDRAW | DELEGATE_PERMISSION_DEPOSIT;
+
+/**
+ * 監査ログに保持するエントリ数
+ * 
+ * 監査ログはリングバッファのため、これを超えると古いエントリから上書きされます。
+ */
+pub const AUDIT_LOG_CAPACITY: usize = 64;
+
+/**
+ * read_audit_log命令で一度に返すエントリの最大数
+ * 
+ * 命令の戻り値は1024バイトに制限されているため、それに収まる件数に制限します。
+ */
+pub const MAX_AUDIT_LOG_READ: u8 = 10;
+
+/**
+ * 監査ログの操作の種類
+ * 
+ * 設定変更では、変更した設定ごとに異なる種類を記録します。
+ * counterpartyには対象のアドレス（委任者、送金先、ミントなど）を、amountには変更後の値を記録します。
+ */
+pub const AUDIT_ACTION_DEPOSIT: u8 = 1;                        // 預け入れ
+pub const AUDIT_ACTION_WITHDRAW: u8 = 2;                       // 引き出し
+pub const AUDIT_ACTION_OWNERSHIP_TRANSFER_INITIATED: u8 = 3;   // 所有権譲渡の開始
+pub const AUDIT_ACTION_OWNERSHIP_TRANSFER_ACCEPTED: u8 = 4;    // 所有権譲渡の承認
+pub const AUDIT_ACTION_OWNERSHIP_TRANSFER_CANCELLED: u8 = 5;   // 所有権譲渡のキャンセル
+pub const AUDIT_ACTION_OWNERSHIP_TRANSFERRED: u8 = 6;          // 所有権の移転
+pub const AUDIT_ACTION_SET_TIMELOCK: u8 = 7;                   // タイムロックの設定（amountはロック期限）
+pub const AUDIT_ACTION_SET_LOCK_MODE: u8 = 8;                  // ロックモードの設定（amountは0が変更可能、1が延長のみ）
+pub const AUDIT_ACTION_ADD_DELEGATE: u8 = 9;                   // 委任者の追加（amountは引き出し枠）
+pub const AUDIT_ACTION_REMOVE_DELEGATE: u8 = 10;               // 委任者の削除
+pub const AUDIT_ACTION_SET_MULTISIG: u8 = 11;                  // 多重署名の設定（amountは必要な署名数）
+pub const AUDIT_ACTION_SET_APPROVAL_TIERS: u8 = 12;            // 承認段階の設定（amountは段階の数）
+pub const AUDIT_ACTION_SET_WITHDRAWAL_LIMIT: u8 = 13;          // 引き出し制限の設定（counterpartyはミント）
+pub const AUDIT_ACTION_SET_WITHDRAWAL_WINDOW: u8 = 14;         // 期間ごとの引き出し枠の設定（amountは引き出し枠）
+pub const AUDIT_ACTION_SET_WITHDRAWAL_DELAY: u8 = 15;          // 引き出し待機時間の設定
+pub const AUDIT_ACTION_ENABLE_ALLOWLIST: u8 = 16;              // 許可リストの有効化（amountは待機時間）
+pub const AUDIT_ACTION_DISABLE_ALLOWLIST: u8 = 17;             // 許可リストの無効化（amountは待機時間）
+pub const AUDIT_ACTION_ADD_ALLOWLIST_ENTRY: u8 = 18;           // 許可リストへの送金先の追加
+pub const AUDIT_ACTION_REMOVE_ALLOWLIST_ENTRY: u8 = 19;        // 許可リストからの送金先の削除
+pub const AUDIT_ACTION_SET_GUARDIANS: u8 = 20;                 // ガーディアンの設定（amountはガーディアンの数）
+pub const AUDIT_ACTION_PAUSE: u8 = 21;                         // 一時停止
+pub const AUDIT_ACTION_UNPAUSE: u8 = 22;                       // 一時停止の解除
+pub const AUDIT_ACTION_SET_RECOVERY: u8 = 23;                  // リカバリーの設定（amountは必要な承認数）
+pub const AUDIT_ACTION_SET_INHERITANCE: u8 = 24;               // 相続の設定（counterpartyは受益者、amountは無活動期間）
+pub const AUDIT_ACTION_REGISTER_MINT: u8 = 25;                 // ミントの登録
+pub const AUDIT_ACTION_UNREGISTER_MINT: u8 = 26;               // ミントの登録解除
+pub const AUDIT_ACTION_SET_TRANSACTION_TTL: u8 = 27;           // 保留中トランザクションの有効期間の設定
+
 /**
  * SimpleVaultプログラム
  * 
@@ -58,21 +217,72 @@ pub mod simple_vault {
      * 新しい金庫アカウントを作成し、初期設定を行います。
      * この命令は金庫を使用する前に一度だけ呼び出す必要があります。
      * 
+     * 1つのウォレットで複数の金庫（給与用、運用資金用、準備金用など）を持てるように、
+     * 金庫は作成者のアドレスと金庫IDの組み合わせから派生します。
+     * 作成者は変更されないため、所有権が譲渡されても金庫のアドレスは変わりません。
+     * 
      * @param ctx - 金庫の初期化に必要なアカウント情報を含むコンテキスト
+     * @param vault_id - 作成者ごとに金庫を識別するID
      * @return Result<()> - 操作の成功または失敗
      */
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+    pub fn initialize(ctx: Context<Initialize>, vault_id: u64) -> Result<()> {
+        check_mint_extensions(&ctx.accounts.mint)?;
+        
         let vault = &mut ctx.accounts.vault;
         vault.owner = ctx.accounts.owner.key();
+        vault.creator = ctx.accounts.owner.key();
+        vault.vault_id = vault_id;
         vault.token_account = ctx.accounts.vault_token_account.key();
         vault.bump = ctx.bumps.vault;
         vault.lock_until = 0; // デフォルトではロックなし
         vault.delegates = Vec::new(); // デフォルトでは委任なし
         vault.multisig_threshold = 1; // デフォルトでは単一署名
         vault.multisig_signers = Vec::new(); // デフォルトでは追加の署名者なし
-        vault.pending_transactions = Vec::new(); // 保留中のトランザクションなし
+        vault.transaction_count = 0; // 保留中トランザクションの連番
+        vault.open_transactions = 0; // 保留中のトランザクションなし
         vault.max_withdrawal_limit = u64::MAX; // デフォルトでは制限なし
         vault.transfer_ownership_to = None; // 所有権譲渡先はなし
+        vault.max_delegates = DEFAULT_MAX_DELEGATES;
+        vault.max_signers = DEFAULT_MAX_SIGNERS;
+        vault.transaction_ttl = DEFAULT_TRANSACTION_TTL;
+        vault.config_version = 0; // 署名者設定のバージョン
+        vault.window_length = 0; // デフォルトでは期間ごとの制限なし
+        vault.window_limit = u64::MAX;
+        vault.window_bucket = 0;
+        vault.window_spent = [0; WINDOW_BUCKETS];
+        vault.allowlist_enabled = false; // デフォルトでは送金先の制限なし
+        vault.allowlist_delay = DEFAULT_ALLOWLIST_DELAY;
+        vault.pending_allowlist = None; // 待機中の許可リストの緩和なし
+        vault.allowlist = Vec::new();
+        vault.max_allowlist = DEFAULT_MAX_ALLOWLIST;
+        vault.withdrawal_delay = 0; // デフォルトでは引き出しの待機時間なし
+        vault.withdrawal_request_count = 0; // 引き出し予約の連番
+        vault.open_withdrawal_requests = 0; // 実行待ちの引き出し予約なし
+        vault.approval_tiers = Vec::new(); // デフォルトでは金額によらずmultisig_thresholdを使用
+        vault.lock_mode = LockMode::Flexible; // デフォルトではロックの短縮が可能
+        vault.reserved_amount = 0; // 権利確定スケジュールに確保された金額
+        vault.vesting_schedule_count = 0; // 権利確定スケジュールの連番
+        vault.payment_stream_count = 0; // 定期支払いの連番
+        vault.open_payment_streams = 0; // 停止されていない定期支払いなし
+        vault.guardians = Vec::new(); // デフォルトではガーディアンなし
+        vault.paused = false;
+        vault.recovery_threshold = 0; // デフォルトではリカバリー無効
+        vault.recovery_delay = DEFAULT_RECOVERY_DELAY;
+        vault.pending_recovery = None;
+        vault.last_owner_activity = Clock::get()?.unix_timestamp as u64;
+        vault.inactivity_period = 0; // デフォルトでは相続なし
+        vault.beneficiary = None;
+        vault.audit_log_enabled = false; // 監査ログはinit_audit_log命令で作成
+        vault.mint = ctx.accounts.mint.key();
+        vault.mints = Vec::new(); // デフォルトでは初期化時のトークンのみ
+        
+        emit!(VaultInitialized {
+            vault: vault.key(),
+            owner: vault.owner,
+            vault_id,
+            token_account: vault.token_account,
+            timestamp: vault.last_owner_activity,
+        });
         Ok(())
     }
 
@@ -80,21 +290,62 @@ pub mod simple_vault {
      * 預け入れ命令（deposit）
      * 
      * ユーザーのトークンアカウントから金庫のトークンアカウントにトークンを移動します。
+     * 初期化時のトークンまたは登録済みのミントのトークンを預け入れることができます。
+     * 転送手数料付きのミントでは、手数料を差し引いて金庫が実際に受け取った量を記録します。
+     * 預け入れができるのは所有者と、預け入れ権限を持つ有効期限内の委任者のみです。
      * 
      * @param ctx - 預け入れに必要なアカウント情報を含むコンテキスト
      * @param amount - 預け入れるトークンの量
+     * @param mint - 預け入れるトークンのミント
      * @return Result<()> - 操作の成功または失敗
      */
-    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+    pub fn deposit(ctx: Context<Deposit>, amount: u64, mint: Pubkey) -> Result<()> {
+        // Only the owner and delegates with the deposit permission can deposit
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        let depositor = ctx.accounts.owner.key();
+        let vault = &mut ctx.accounts.vault;
+        if vault.owner == depositor {
+            vault.record_owner_activity(current_timestamp);
+        } else {
+            vault.delegates.iter()
+                .find(|record| record.delegate == depositor)
+                .ok_or(VaultError::Unauthorized)?
+                .authorize(DELEGATE_PERMISSION_DEPOSIT, current_timestamp)?;
+        }
+        
+        let balance_before = ctx.accounts.vault_token_account.amount;
+        
         // Transfer tokens from user to vault
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
             to: ctx.accounts.vault_token_account.to_account_info(),
             authority: ctx.accounts.owner.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, amount)?;
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.token_mint.decimals)?;
+        
+        // Transfer-fee mints withhold part of the amount, so record what actually arrived
+        ctx.accounts.vault_token_account.reload()?;
+        let received = ctx.accounts.vault_token_account.amount.saturating_sub(balance_before);
+        
+        emit!(Deposited {
+            vault: ctx.accounts.vault.key(),
+            depositor: ctx.accounts.owner.key(),
+            mint,
+            amount,
+            received,
+            timestamp: current_timestamp,
+        });
+        record_audit_entry(
+            &ctx.accounts.vault,
+            &ctx.accounts.audit_log,
+            AUDIT_ACTION_DEPOSIT,
+            ctx.accounts.owner.key(),
+            ctx.accounts.user_token_account.key(),
+            received,
+        )?;
         
         Ok(())
     }
@@ -105,64 +356,156 @@ pub mod simple_vault {
      * 金庫のトークンアカウントからユーザーのトークンアカウントにトークンを移動します。
      * 多重署名が設定されている場合は、保留中のトランザクションとして記録され、
      * 必要な署名数が集まるまで実行されません。
+     * 承認段階が設定されている場合、必要な署名数は引き出し金額に応じて決まり、
+     * 1署名で足りる少額の引き出しは即時に実行されます。
      * 
      * 引き出しには以下の条件が確認されます：
      * - 呼び出し者が所有者または委任された権限を持っていること
-     * - 金庫がタイムロックされていないこと
-     * - 引き出し金額が制限を超えていないこと
+     * - 委任者の場合は、有効期限内で引き出し権限を持ち、引き出し枠が残っていること
+     *   （委任者の提案は承認に数えず、引き出し枠は実行時に差し引かれます）
+     * - 金庫が一時停止またはタイムロックされていないこと
+     * - 引き出し金額がミントごとの制限を超えていないこと
+     * - 引き出し金額が現在の期間の残り引き出し枠を超えていないこと
+     * - 許可リストが有効な場合は、送金先が有効な許可リストに含まれていること
+     * - 権利確定スケジュールに確保された金額に手を付けないこと
+     * 
+     * 委任者の引き出し枠、期間ごとの引き出し枠、承認段階、権利確定スケジュールは
+     * 初期化時のトークンの単位で設定されているため、登録済みのミントには適用されません。
+     * 登録済みのミントは所有者のみが引き出すことができ、多重署名の場合はmultisig_thresholdの承認が必要です。
+     * 
+     * 引き出しの待機時間が設定されている単一署名の金庫では、この命令は使用できず、
+     * request_withdrawal命令で予約してから待機時間後にexecute_withdrawal命令で実行します。
      * 
      * @param ctx - 引き出しに必要なアカウント情報を含むコンテキスト
      * @param amount - 引き出すトークンの量
+     * @param mint - 引き出すトークンのミント
      * @return Result<()> - 操作の成功または失敗
      */
-    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64, mint: Pubkey) -> Result<()> {
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        
         // Verify owner or delegate
-        let vault = &ctx.accounts.vault;
-        let is_owner = vault.owner == ctx.accounts.owner.key();
-        let is_delegate = vault.delegates.contains(&ctx.accounts.owner.key());
+        let caller = ctx.accounts.owner.key();
+        let vault = &mut ctx.accounts.vault;
+        let is_primary = mint == vault.mint;
+        let is_owner = vault.owner == caller;
+        if is_owner {
+            vault.record_owner_activity(current_timestamp);
+        } else {
+            let record = vault.delegates.iter()
+                .find(|record| record.delegate == caller)
+                .ok_or(VaultError::Unauthorized)?;
+            
+            // Delegate allowances are denominated in the primary mint
+            require!(is_primary, VaultError::DelegatePermissionDenied);
+            
+            // The allowance is spent once the withdrawal is carried out
+            record.authorize(DELEGATE_PERMISSION_WITHDRAW, current_timestamp)?;
+            require!(
+                amount <= record.remaining_allowance(current_timestamp),
+                VaultError::ExceedsDelegateAllowance
+            );
+        }
         
-        require!(is_owner || is_delegate, VaultError::Unauthorized);
+        // Check if the vault is paused
+        require!(!vault.paused, VaultError::VaultPaused);
         
         // Check if the vault is locked
-        let current_timestamp = Clock::get()?.unix_timestamp as u64;
         require!(current_timestamp >= vault.lock_until, VaultError::VaultLocked);
 
         // Check withdrawal limit
-        require!(amount <= vault.max_withdrawal_limit, VaultError::ExceedsWithdrawalLimit);
+        require!(amount <= vault.withdrawal_limit(&mint), VaultError::ExceedsWithdrawalLimit);
 
-        // Check if multisig is required (threshold > 1)
-        if vault.multisig_threshold > 1 {
-            // This is a multisig vault, so we need to create a pending transaction
+        // Check the destination against the allowlist
+        vault.check_destination(&ctx.accounts.user_token_account, current_timestamp)?;
+
+        // Tokens reserved for vesting cannot be withdrawn
+        if is_primary {
+            vault.check_unreserved(ctx.accounts.vault_token_account.amount, amount)?;
+        }
+
+        // Check if multisig is required for this amount (threshold > 1).
+        // Approval tiers are denominated in the primary mint.
+        let threshold = if is_primary {
+            vault.withdrawal_threshold(amount)
+        } else {
+            vault.multisig_threshold
+        };
+        if threshold > 1 {
+            // This is a multisig withdrawal, so we need to create a pending transaction
             let vault_mut = &mut ctx.accounts.vault;
-            let tx_id = vault_mut.pending_transactions.len() as u64;
+            let pending_tx = ctx.accounts.pending_transaction.as_mut()
+                .ok_or(VaultError::InvalidPendingTransactionAccount)?;
+            let tx_id = vault_mut.open_transaction();
             
             // Create pending transaction
-            let pending_tx = PendingTransaction {
+            pending_tx.set_inner(PendingTransaction {
+                vault: vault_mut.key(),
                 id: tx_id,
+                proposer: ctx.accounts.owner.key(),
                 transaction_type: TransactionType::Withdraw,
                 amount,
                 destination: ctx.accounts.user_token_account.key(),
                 new_owner: None,
-                signers: vec![ctx.accounts.owner.key()],
-                executed: false,
+                // A delegate's proposal is not an approval
+                signers: if is_owner { vec![caller] } else { Vec::new() },
+                rejections: Vec::new(),
+                threshold,
+                status: TransactionStatus::Pending,
                 created_at: current_timestamp,
-            };
-            
-            // Add to pending transactions
-            vault_mut.pending_transactions.push(pending_tx);
+                expires_at: vault_mut.transaction_expiry(current_timestamp),
+                config_version: vault_mut.config_version,
+                bump: ctx.bumps.pending_transaction,
+            });
+            emit_transaction_proposed(pending_tx);
             
             // Return early, the transaction is not executed yet
             return Ok(());
         }
+        
+        // A pending transaction account is only created in multisig mode
+        require!(
+            ctx.accounts.pending_transaction.is_none(),
+            VaultError::InvalidPendingTransactionAccount
+        );
+
+        // Delayed vaults must announce withdrawals first
+        require!(ctx.accounts.vault.withdrawal_delay == 0, VaultError::WithdrawalDelayRequired);
+
+        // Charge the rolling window budget
+        if is_primary {
+            ctx.accounts.vault.consume_window_budget(amount, current_timestamp)?;
+        }
+
+        // Delegates spend down their own allowance
+        if !is_owner {
+            ctx.accounts.vault.spend_delegate_allowance(caller, amount, current_timestamp)?;
+        }
 
         // Single-sig mode, execute immediately
         execute_withdraw(
-            ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.vault,
             ctx.accounts.vault_token_account.to_account_info(),
+            &ctx.accounts.token_mint,
             ctx.accounts.user_token_account.to_account_info(),
             ctx.accounts.token_program.to_account_info(),
             amount,
-            ctx.accounts.vault.bump,
+        )?;
+        
+        emit!(Withdrawn {
+            vault: ctx.accounts.vault.key(),
+            actor: ctx.accounts.owner.key(),
+            destination: ctx.accounts.user_token_account.key(),
+            amount,
+            timestamp: current_timestamp,
+        });
+        record_audit_entry(
+            &ctx.accounts.vault,
+            &ctx.accounts.audit_log,
+            AUDIT_ACTION_WITHDRAW,
+            ctx.accounts.owner.key(),
+            ctx.accounts.user_token_account.key(),
+            amount,
         )?;
         
         Ok(())
@@ -186,6 +529,8 @@ pub mod simple_vault {
      * 
      * 金庫に一定期間のロックをかけます。ロック期間中は引き出しができなくなります。
      * 長期保管や不正引き出しの防止に有効です。
+     * ロックモードがExtendOnlyの場合は、現在のロック期限より前に短縮することはできません。
+     * 多重署名が設定されている場合は、保留中のトランザクションとして記録されます。
      * 
      * @param ctx - タイムロック設定に必要なアカウント情報を含むコンテキスト
      * @param lock_duration - ロック期間（秒）
@@ -193,38 +538,124 @@ pub mod simple_vault {
      */
     pub fn set_timelock(ctx: Context<SetTimelock>, lock_duration: u64) -> Result<()> {
         // Verify owner
-        let vault = &mut ctx.accounts.vault;
-        require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
         
-        // Set the lock until timestamp (current time + duration)
-        let current_timestamp = Clock::get()?.unix_timestamp as u64;
-        vault.lock_until = current_timestamp + lock_duration;
+        // Set the lock, or propose it if multisig is enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::SetTimelock { lock_duration },
+        )
+    }
+
+    /**
+     * 期限指定タイムロック設定命令（setTimelockUntil）
+     * 
+     * 指定したUNIXタイムスタンプまで金庫をロックします。
+     * ロックモードがExtendOnlyの場合は、現在のロック期限より前に短縮することはできません。
+     * 多重署名が設定されている場合は、保留中のトランザクションとして記録されます。
+     * 
+     * @param ctx - タイムロック設定に必要なアカウント情報を含むコンテキスト
+     * @param unlock_at - ロックが解除されるUNIXタイムスタンプ
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn set_timelock_until(ctx: Context<SetTimelock>, unlock_at: u64) -> Result<()> {
+        // Verify owner
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
         
-        Ok(())
+        // Set the lock, or propose it if multisig is enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::SetTimelockUntil { unlock_at },
+        )
+    }
+
+    /**
+     * ロックモード設定命令（setLockMode）
+     * 
+     * タイムロックの変更方法を設定します。
+     * ExtendOnlyに設定すると、ロック期限は延長のみ可能になり、鍵が漏洩してもロックを解除できなくなります。
+     * ExtendOnlyからFlexibleに戻すことはできません。
+     * 多重署名が設定されている場合は、保留中のトランザクションとして記録されます。
+     * 
+     * @param ctx - タイムロック設定に必要なアカウント情報を含むコンテキスト
+     * @param mode - ロックモード
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn set_lock_mode(ctx: Context<SetTimelock>, mode: LockMode) -> Result<()> {
+        // Verify owner
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        
+        // Set the mode, or propose it if multisig is enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::SetLockMode { mode },
+        )
     }
 
     /**
      * 委任者追加命令（addDelegate）
      * 
      * 金庫の操作権限を他のアドレスに委任します。
-     * 委任された権限を持つアドレスは引き出し操作を行うことができます。
+     * 委任者ごとに引き出し枠、枠の補充期間、有効期限、権限を指定できます。
+     * 既に委任されているアドレスを指定した場合は、委任内容を置き換えます。
+     * 多重署名が設定されている場合は、保留中のトランザクションとして記録されます。
      * 
      * @param ctx - 委任者追加に必要なアカウント情報を含むコンテキスト
      * @param delegate - 委任するアドレス
+     * @param allowance - 引き出し枠（u64::MAXは制限なし）
+     * @param refill_period - 引き出し枠の補充期間（秒、0は補充なし）
+     * @param expires_at - 委任の有効期限のUNIXタイムスタンプ（0は期限なし）
+     * @param permissions - 許可する操作の権限フラグ
      * @return Result<()> - 操作の成功または失敗
      */
-    pub fn add_delegate(ctx: Context<ManageDelegate>, delegate: Pubkey) -> Result<()> {
+    pub fn add_delegate(
+        ctx: Context<ManageDelegate>,
+        delegate: Pubkey,
+        allowance: u64,
+        refill_period: u64,
+        expires_at: u64,
+        permissions: u8,
+    ) -> Result<()> {
         // Verify owner
-        let vault = &mut ctx.accounts.vault;
-        require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
         
-        // Check if already a delegate
-        if !vault.delegates.contains(&delegate) {
-            // Add the delegate
-            vault.delegates.push(delegate);
-        }
+        // Validate permissions
+        require!(
+            permissions != 0 && permissions & !DELEGATE_PERMISSION_ALL == 0,
+            VaultError::InvalidDelegatePermissions
+        );
         
-        Ok(())
+        let record = DelegateRecord {
+            delegate,
+            allowance,
+            refill_period,
+            spent: 0,
+            period_start: 0, // Set when the delegate is added
+            expires_at,
+            permissions,
+        };
+        
+        // Add the delegate, or propose it if multisig is enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::AddDelegate { record },
+        )
     }
 
     /**
@@ -232,6 +663,7 @@ pub mod simple_vault {
      * 
      * 委任した操作権限を削除します。
      * 削除されたアドレスは引き出し操作を行うことができなくなります。
+     * 権限を減らす操作のため、多重署名が設定されていても即時に反映されます。
      * 
      * @param ctx - 委任者削除に必要なアカウント情報を含むコンテキスト
      * @param delegate - 削除する委任アドレス
@@ -241,10 +673,32 @@ pub mod simple_vault {
         // Verify owner
         let vault = &mut ctx.accounts.vault;
         require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        vault.record_owner_activity(Clock::get()?.unix_timestamp as u64);
+        
+        // Removal takes effect immediately, so no pending transaction is created
+        require!(
+            ctx.accounts.pending_transaction.is_none(),
+            VaultError::InvalidPendingTransactionAccount
+        );
         
         // Remove the delegate if it exists
-        if let Some(index) = vault.delegates.iter().position(|&d| d == delegate) {
+        if let Some(index) = vault.delegates.iter().position(|record| record.delegate == delegate) {
             vault.delegates.remove(index);
+            
+            emit!(DelegateRemoved {
+                vault: vault.key(),
+                actor: ctx.accounts.owner.key(),
+                delegate,
+                timestamp: vault.last_owner_activity,
+            });
+            record_audit_entry(
+                vault,
+                &ctx.accounts.audit_log,
+                AUDIT_ACTION_REMOVE_DELEGATE,
+                ctx.accounts.owner.key(),
+                delegate,
+                0,
+            )?;
         }
         
         Ok(())
@@ -255,6 +709,7 @@ pub mod simple_vault {
      * 
      * 金庫に多重署名（マルチシグ）の設定を行います。
      * 設定後は、指定した閾値以上の署名者が承認しなければ引き出し等の操作が実行されなくなります。
+     * 既に多重署名が設定されている場合は、設定の変更自体が保留中のトランザクションとして記録されます。
      * 
      * @param ctx - 多重署名設定に必要なアカウント情報を含むコンテキスト
      * @param threshold - 必要な署名者数の閾値
@@ -263,28 +718,51 @@ pub mod simple_vault {
      */
     pub fn set_multisig(ctx: Context<SetMultisig>, threshold: u8, signers: Vec<Pubkey>) -> Result<()> {
         // Verify owner
-        let vault = &mut ctx.accounts.vault;
-        require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
-        
-        // Validate threshold
-        require!(threshold > 0, VaultError::InvalidThreshold);
-        require!(
-            threshold <= signers.len() as u8 + 1, // +1 for owner
-            VaultError::InvalidThreshold
-        );
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
         
-        // Set multisig configuration
-        vault.multisig_threshold = threshold;
-        vault.multisig_signers = signers;
+        // Set multisig configuration, or propose it if multisig is already enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::SetMultisig { threshold, signers },
+        )
+    }
+
+    /**
+     * 承認段階設定命令（setApprovalTiers）
+     * 
+     * 引き出し金額に応じて必要な署名数を変える承認段階を設定します。
+     * 各段階は上限金額の昇順で指定し、金額が上限以下となる最初の段階の署名数が適用されます。
+     * どの段階にも該当しない金額にはmultisig_thresholdが適用されます。
+     * 多重署名が設定されている場合は、保留中のトランザクションとして記録されます。
+     * 
+     * @param ctx - 多重署名設定に必要なアカウント情報を含むコンテキスト
+     * @param tiers - 承認段階のリスト（空にすると金額によらずmultisig_thresholdを使用）
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn set_approval_tiers(ctx: Context<SetMultisig>, tiers: Vec<ApprovalTier>) -> Result<()> {
+        // Verify owner
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
         
-        Ok(())
+        // Set the tiers, or propose them if multisig is enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::SetApprovalTiers { tiers },
+        )
     }
 
     /**
      * トランザクション承認命令（approveTransaction）
      * 
      * 保留中のトランザクションを承認します。
-     * 多重署名が設定されている場合、必要な署名数に達すると実際のトランザクションが実行されます。
+     * 必要な署名数に達したトランザクションは、execute_transaction命令で実行されます。
      * 
      * @param ctx - トランザクション承認に必要なアカウント情報を含むコンテキスト
      * @param tx_id - 承認するトランザクションのID
@@ -292,6 +770,7 @@ pub mod simple_vault {
      */
     pub fn approve_transaction(ctx: Context<ApproveTransaction>, tx_id: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
+        let pending_tx = &mut ctx.accounts.pending_transaction;
         let current_signer = ctx.accounts.signer.key();
         
         // Verify signer is owner or in multisig_signers
@@ -299,62 +778,198 @@ pub mod simple_vault {
         let is_multisig_signer = vault.multisig_signers.contains(&current_signer);
         
         require!(is_owner || is_multisig_signer, VaultError::Unauthorized);
+        if is_owner {
+            vault.record_owner_activity(Clock::get()?.unix_timestamp as u64);
+        }
         
-        // Find the pending transaction
-        if let Some(tx_index) = vault.pending_transactions.iter().position(|tx| tx.id == tx_id && !tx.executed) {
-            // Add the signer if not already added
-            if !vault.pending_transactions[tx_index].signers.contains(&current_signer) {
-                vault.pending_transactions[tx_index].signers.push(current_signer);
-            }
-            
-            // Check if we have enough signatures
-            let has_enough_signatures = vault.pending_transactions[tx_index].signers.len() as u8 >= vault.multisig_threshold;
-            let transaction_type = vault.pending_transactions[tx_index].transaction_type;
-            
-            if has_enough_signatures {
-                match transaction_type {
-                    TransactionType::Withdraw => {
-                        // Get the required values before modifying the transaction
-                        let amount = vault.pending_transactions[tx_index].amount;
-                        let max_limit = vault.max_withdrawal_limit;
-                        let bump = vault.bump;
-
-                        // Check withdrawal limit
-                        require!(amount <= max_limit, VaultError::ExceedsWithdrawalLimit);
-
-                        // Drop mutable reference to vault before calling execute_withdraw
-                        drop(vault);
-
-                        execute_withdraw(
-                            ctx.accounts.vault.to_account_info(),
-                            ctx.accounts.vault_token_account.to_account_info(),
-                            ctx.accounts.destination_token_account.to_account_info(),
-                            ctx.accounts.token_program.to_account_info(),
-                            amount,
-                            bump,
-                        )?;
-                        
-                        // Get vault reference again
-                        let vault = &mut ctx.accounts.vault;
-                        vault.pending_transactions[tx_index].executed = true;
-                    },
-                    TransactionType::TransferOwnership => {
-                        // Get the new owner before modifying the transaction
-                        if let Some(new_owner) = vault.pending_transactions[tx_index].new_owner {
-                            // Update the owner
-                            vault.owner = new_owner;
-                            // Clear pending transfer
-                            vault.transfer_ownership_to = None;
-                            // Clear delegates as they were for the previous owner
-                            vault.delegates.clear();
-                            // Mark as executed
-                            vault.pending_transactions[tx_index].executed = true;
-                        }
-                    },
+        // Only transactions that are still waiting for approval can be approved
+        require!(
+            pending_tx.id == tx_id && pending_tx.status == TransactionStatus::Pending,
+            VaultError::TransactionNotFound
+        );
+        
+        // Stale transactions must be cleaned up with expire_transaction instead
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        require!(!pending_tx.is_expired(current_timestamp), VaultError::ProposalExpired);
+        require!(pending_tx.config_version == vault.config_version, VaultError::StaleTransaction);
+        
+        // Add the signer if not already added, replacing an earlier rejection
+        if !pending_tx.signers.contains(&current_signer) {
+            pending_tx.signers.push(current_signer);
+        }
+        pending_tx.rejections.retain(|&r| r != current_signer);
+        
+        emit!(TransactionApproved {
+            vault: vault.key(),
+            tx_id,
+            signer: current_signer,
+            approvals: pending_tx.signers.len() as u8,
+            threshold: pending_tx.threshold,
+            timestamp: current_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /**
+     * トランザクション実行命令（executeTransaction）
+     * 
+     * 必要な署名数の承認が集まった保留中のトランザクションを実行します。
+     * 承認済みのトランザクションの内容は変更できないため、誰でも実行できます。
+     * 
+     * 引き出しの場合、送金先は提案時に指定されたトークンアカウントと一致し、
+     * 金庫と同じミントである必要があります。
+     * 
+     * @param ctx - トランザクション実行に必要なアカウント情報を含むコンテキスト
+     * @param tx_id - 実行するトランザクションのID
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn execute_transaction(ctx: Context<ExecuteTransaction>, tx_id: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let pending_tx = &mut ctx.accounts.pending_transaction;
+        
+        require!(
+            pending_tx.id == tx_id && pending_tx.status == TransactionStatus::Pending,
+            VaultError::TransactionNotFound
+        );
+        
+        // Stale transactions must be cleaned up with expire_transaction instead
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        require!(!pending_tx.is_expired(current_timestamp), VaultError::ProposalExpired);
+        require!(pending_tx.config_version == vault.config_version, VaultError::StaleTransaction);
+        
+        // Check if we have enough signatures
+        require!(
+            pending_tx.signers.len() >= pending_tx.threshold as usize,
+            VaultError::InsufficientApprovals
+        );
+        
+        match pending_tx.transaction_type {
+            TransactionType::Withdraw => {
+                // Paused vaults do not move funds, even with approvals
+                require!(!vault.paused, VaultError::VaultPaused);
+                
+                // The destination must be the one that was approved
+                let destination = ctx.accounts.destination_token_account.as_ref()
+                    .ok_or(VaultError::DestinationMismatch)?;
+                require!(destination.key() == pending_tx.destination, VaultError::DestinationMismatch);
+                require!(destination.mint == ctx.accounts.vault_token_account.mint, VaultError::InvalidMint);
+                vault.check_destination(destination, current_timestamp)?;
+                
+                // Check withdrawal limit
+                let amount = pending_tx.amount;
+                let mint = destination.mint;
+                require!(amount <= vault.withdrawal_limit(&mint), VaultError::ExceedsWithdrawalLimit);
+                if mint == vault.mint {
+                    vault.check_unreserved(ctx.accounts.vault_token_account.amount, amount)?;
+                    vault.consume_window_budget(amount, current_timestamp)?;
                 }
-            }
-        } else {
-            return Err(VaultError::TransactionNotFound.into());
+                
+                // Delegate proposals spend the allowance only when executed
+                if pending_tx.proposer != vault.owner {
+                    vault.spend_delegate_allowance(pending_tx.proposer, amount, current_timestamp)?;
+                }
+
+                execute_withdraw(
+                    &ctx.accounts.vault,
+                    ctx.accounts.vault_token_account.to_account_info(),
+                    &ctx.accounts.token_mint,
+                    destination.to_account_info(),
+                    ctx.accounts.token_program.to_account_info(),
+                    amount,
+                )?;
+                
+                emit!(Withdrawn {
+                    vault: ctx.accounts.vault.key(),
+                    actor: ctx.accounts.pending_transaction.proposer,
+                    destination: destination.key(),
+                    amount,
+                    timestamp: current_timestamp,
+                });
+                record_audit_entry(
+                    &ctx.accounts.vault,
+                    &ctx.accounts.audit_log,
+                    AUDIT_ACTION_WITHDRAW,
+                    ctx.accounts.pending_transaction.proposer,
+                    destination.key(),
+                    amount,
+                )?;
+                
+                ctx.accounts.pending_transaction.status = TransactionStatus::Executed;
+                ctx.accounts.vault.settle_transaction();
+            },
+            TransactionType::CloseVault => {
+                // Closing needs the owner's accounts, so it is executed by close_vault once approved
+                return Err(VaultError::InvalidTransactionType.into());
+            },
+            TransactionType::SetTimelock { .. }
+            | TransactionType::AddDelegate { .. }
+            | TransactionType::SetMultisig { .. }
+            | TransactionType::SetWithdrawalLimit { .. }
+            | TransactionType::SetWithdrawalWindow { .. }
+            | TransactionType::AddAllowlistEntry { .. }
+            | TransactionType::SetAllowlist { .. }
+            | TransactionType::SetWithdrawalDelay { .. }
+            | TransactionType::SetApprovalTiers { .. }
+            | TransactionType::SetTimelockUntil { .. }
+            | TransactionType::SetLockMode { .. }
+            | TransactionType::SetGuardians { .. }
+            | TransactionType::Unpause
+            | TransactionType::SetRecovery { .. }
+            | TransactionType::SetInheritance { .. }
+            | TransactionType::SetMintWithdrawalLimit { .. }
+            | TransactionType::SetTransactionTtl { .. } => {
+                let change = pending_tx.transaction_type.clone();
+                vault.apply_config_change(&change, current_timestamp)?;
+                emit_config_change(vault, &change, pending_tx.proposer, current_timestamp);
+                record_config_change(vault, &ctx.accounts.audit_log, &change, pending_tx.proposer)?;
+                
+                pending_tx.status = TransactionStatus::Executed;
+                vault.settle_transaction();
+            },
+            TransactionType::TransferOwnership => {
+                // An ownership transfer cancelled or replaced by the owner can no longer be executed
+                if vault.transfer_ownership_to != pending_tx.new_owner {
+                    pending_tx.status = TransactionStatus::Cancelled;
+                    vault.settle_transaction();
+                    return Ok(());
+                }
+                
+                // Get the new owner before modifying the transaction
+                if let Some(new_owner) = pending_tx.new_owner {
+                    emit!(OwnershipTransferred {
+                        vault: vault.key(),
+                        previous_owner: vault.owner,
+                        new_owner,
+                        timestamp: current_timestamp,
+                    });
+                    record_audit_entry(
+                        vault,
+                        &ctx.accounts.audit_log,
+                        AUDIT_ACTION_OWNERSHIP_TRANSFERRED,
+                        vault.owner,
+                        new_owner,
+                        0,
+                    )?;
+                    
+                    // Update the owner
+                    vault.rotate_owner(new_owner, current_timestamp);
+                    // Mark as executed
+                    pending_tx.status = TransactionStatus::Executed;
+                    vault.settle_transaction();
+                }
+            },
+        }
+        
+        let pending_tx = &ctx.accounts.pending_transaction;
+        if pending_tx.status == TransactionStatus::Executed {
+            emit!(TransactionExecuted {
+                vault: ctx.accounts.vault.key(),
+                tx_id,
+                proposer: pending_tx.proposer,
+                approvals: pending_tx.signers.len() as u8,
+                timestamp: current_timestamp,
+            });
         }
         
         Ok(())
@@ -365,18 +980,145 @@ pub mod simple_vault {
      * 
      * 1回の取引で引き出せる最大金額を設定します。
      * セキュリティ対策として、不正な大量引き出しのリスクを軽減します。
+     * 多重署名が設定されている場合は、保留中のトランザクションとして記録されます。
      * 
      * @param ctx - 引き出し制限設定に必要なアカウント情報を含むコンテキスト
      * @param limit - 最大引き出し可能額
      * @return Result<()> - 操作の成功または失敗
      */
     pub fn set_withdrawal_limit(ctx: Context<SetWithdrawalLimit>, limit: u64) -> Result<()> {
+        // Verify owner
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        
+        // Set the withdrawal limit, or propose it if multisig is enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::SetWithdrawalLimit { limit },
+        )
+    }
+
+    /**
+     * 期間ごとの引き出し制限設定命令（setWithdrawalWindow）
+     * 
+     * 一定期間内に引き出せる合計金額を設定します。
+     * 期間は引き出しのたびに現在時刻から遡って数えるため、期間の区切りをまたいでも枠を超えて引き出せません。
+     * 1回あたりの制限と異なり、引き出しを繰り返して金庫を空にすることを防ぎます。
+     * 多重署名が設定されている場合は、保留中のトランザクションとして記録されます。
+     * 
+     * @param ctx - 引き出し制限設定に必要なアカウント情報を含むコンテキスト
+     * @param window_length - 期間の長さ（秒、0は制限なし）
+     * @param window_limit - 期間内の最大引き出し可能額
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn set_withdrawal_window(ctx: Context<SetWithdrawalLimit>, window_length: u64, window_limit: u64) -> Result<()> {
+        // Verify owner
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        
+        // Set the window budget, or propose it if multisig is enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::SetWithdrawalWindow { window_length, window_limit },
+        )
+    }
+
+    /**
+     * 許可リスト設定命令（setAllowlist）
+     * 
+     * 送金先の許可リストの有効・無効と、追加した送金先が有効になるまでの待機時間を設定します。
+     * 許可リストが有効な場合、許可リストに含まれない送金先への引き出しは拒否されます。
+     * 無効化や待機時間の短縮は、現在の待機時間が経過してから反映されます。
+     * それまでに制限を緩めない設定をすると、待機中の変更は取り消されます。
+     * 多重署名が設定されている場合は、保留中のトランザクションとして記録されます。
+     * 
+     * @param ctx - 許可リスト管理に必要なアカウント情報を含むコンテキスト
+     * @param enabled - 許可リストを有効にするかどうか
+     * @param delay - 追加した送金先が有効になるまでの待機時間（秒）
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn set_allowlist(ctx: Context<ManageAllowlist>, enabled: bool, delay: u64) -> Result<()> {
+        // Verify owner
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        
+        // Update the allowlist settings, or propose it if multisig is enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::SetAllowlist { enabled, delay },
+        )
+    }
+
+    /**
+     * 許可リスト追加命令（addAllowlistEntry）
+     * 
+     * 送金先のトークンアカウント、またはその所有者のウォレットアドレスを許可リストに追加します。
+     * 追加した送金先は、設定された待機時間が経過するまで有効になりません。
+     * 多重署名が設定されている場合は、保留中のトランザクションとして記録されます。
+     * 
+     * @param ctx - 許可リスト管理に必要なアカウント情報を含むコンテキスト
+     * @param address - 追加するトークンアカウントまたはウォレットのアドレス
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn add_allowlist_entry(ctx: Context<ManageAllowlist>, address: Pubkey) -> Result<()> {
+        // Verify owner
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        
+        // Add the entry, or propose it if multisig is enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::AddAllowlistEntry { address },
+        )
+    }
+
+    /**
+     * 許可リスト削除命令（removeAllowlistEntry）
+     * 
+     * 送金先を許可リストから削除します。
+     * 権限を減らす操作のため、多重署名が設定されていても即時に反映されます。
+     * 
+     * @param ctx - 許可リスト管理に必要なアカウント情報を含むコンテキスト
+     * @param address - 削除するトークンアカウントまたはウォレットのアドレス
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn remove_allowlist_entry(ctx: Context<ManageAllowlist>, address: Pubkey) -> Result<()> {
         // Verify owner
         let vault = &mut ctx.accounts.vault;
         require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        vault.record_owner_activity(Clock::get()?.unix_timestamp as u64);
+        
+        // Removal takes effect immediately, so no pending transaction is created
+        require!(
+            ctx.accounts.pending_transaction.is_none(),
+            VaultError::InvalidPendingTransactionAccount
+        );
         
-        // Set the withdrawal limit
-        vault.max_withdrawal_limit = limit;
+        // Remove the entry if it exists
+        if let Some(index) = vault.allowlist.iter().position(|entry| entry.address == address) {
+            vault.allowlist.remove(index);
+            
+            record_audit_entry(
+                vault,
+                &ctx.accounts.audit_log,
+                AUDIT_ACTION_REMOVE_ALLOWLIST_ENTRY,
+                ctx.accounts.owner.key(),
+                address,
+                0,
+            )?;
+        }
         
         Ok(())
     }
@@ -395,29 +1137,58 @@ pub mod simple_vault {
         // Verify owner
         let vault = &mut ctx.accounts.vault;
         require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        vault.record_owner_activity(Clock::get()?.unix_timestamp as u64);
         
         // Set the new owner as pending
         vault.transfer_ownership_to = Some(new_owner);
         
+        emit!(OwnershipTransferInitiated {
+            vault: vault.key(),
+            owner: vault.owner,
+            new_owner,
+            timestamp: vault.last_owner_activity,
+        });
+        record_audit_entry(
+            vault,
+            &ctx.accounts.audit_log,
+            AUDIT_ACTION_OWNERSHIP_TRANSFER_INITIATED,
+            vault.owner,
+            new_owner,
+            0,
+        )?;
+        
         // If multisig is enabled, create a pending transaction
         if vault.multisig_threshold > 1 {
+            let pending_tx = ctx.accounts.pending_transaction.as_mut()
+                .ok_or(VaultError::InvalidPendingTransactionAccount)?;
             let current_timestamp = Clock::get()?.unix_timestamp as u64;
-            let tx_id = vault.pending_transactions.len() as u64;
+            let tx_id = vault.open_transaction();
             
             // Create pending transaction
-            let pending_tx = PendingTransaction {
+            pending_tx.set_inner(PendingTransaction {
+                vault: vault.key(),
                 id: tx_id,
+                proposer: ctx.accounts.owner.key(),
                 transaction_type: TransactionType::TransferOwnership,
                 amount: 0, // Not relevant for ownership transfer
                 destination: Pubkey::default(), // Not relevant for ownership transfer
                 new_owner: Some(new_owner),
                 signers: vec![ctx.accounts.owner.key()],
-                executed: false,
+                rejections: Vec::new(),
+                threshold: vault.multisig_threshold,
+                status: TransactionStatus::Pending,
                 created_at: current_timestamp,
-            };
-            
-            // Add to pending transactions
-            vault.pending_transactions.push(pending_tx);
+                expires_at: vault.transaction_expiry(current_timestamp),
+                config_version: vault.config_version,
+                bump: ctx.bumps.pending_transaction,
+            });
+            emit_transaction_proposed(pending_tx);
+        } else {
+            // A pending transaction account is only created in multisig mode
+            require!(
+                ctx.accounts.pending_transaction.is_none(),
+                VaultError::InvalidPendingTransactionAccount
+            );
         }
         
         Ok(())
@@ -440,14 +1211,40 @@ pub mod simple_vault {
             Some(pending_owner) => {
                 require!(pending_owner == ctx.accounts.new_owner.key(), VaultError::Unauthorized);
                 
+                let current_timestamp = Clock::get()?.unix_timestamp as u64;
+                emit!(OwnershipTransferAccepted {
+                    vault: vault.key(),
+                    new_owner: pending_owner,
+                    timestamp: current_timestamp,
+                });
+                record_audit_entry(
+                    vault,
+                    &ctx.accounts.audit_log,
+                    AUDIT_ACTION_OWNERSHIP_TRANSFER_ACCEPTED,
+                    pending_owner,
+                    vault.owner,
+                    0,
+                )?;
+                
                 // If multisig is not enabled, transfer ownership immediately
                 if vault.multisig_threshold <= 1 {
+                    emit!(OwnershipTransferred {
+                        vault: vault.key(),
+                        previous_owner: vault.owner,
+                        new_owner: pending_owner,
+                        timestamp: current_timestamp,
+                    });
+                    record_audit_entry(
+                        vault,
+                        &ctx.accounts.audit_log,
+                        AUDIT_ACTION_OWNERSHIP_TRANSFERRED,
+                        vault.owner,
+                        pending_owner,
+                        0,
+                    )?;
+                    
                     // Update the owner
-                    vault.owner = pending_owner;
-                    // Clear pending transfer
-                    vault.transfer_ownership_to = None;
-                    // Clear delegates as they were for the previous owner
-                    vault.delegates.clear();
+                    vault.rotate_owner(pending_owner, current_timestamp);
                 }
                 
                 Ok(())
@@ -470,385 +1267,4896 @@ pub mod simple_vault {
         
         // Verify owner
         require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        vault.record_owner_activity(Clock::get()?.unix_timestamp as u64);
         
         // Check if there's a pending transfer
-        require!(vault.transfer_ownership_to.is_some(), VaultError::NoOwnershipTransferPending);
+        let new_owner = vault.transfer_ownership_to
+            .ok_or(VaultError::NoOwnershipTransferPending)?;
         
         // Clear pending transfer
         vault.transfer_ownership_to = None;
         
-        // Remove any pending ownership transfer transactions
-        vault.pending_transactions.retain(|tx| 
-            tx.transaction_type != TransactionType::TransferOwnership || tx.executed
-        );
+        emit!(OwnershipTransferCancelled {
+            vault: vault.key(),
+            owner: vault.owner,
+            new_owner,
+            timestamp: vault.last_owner_activity,
+        });
+        record_audit_entry(
+            vault,
+            &ctx.accounts.audit_log,
+            AUDIT_ACTION_OWNERSHIP_TRANSFER_CANCELLED,
+            vault.owner,
+            new_owner,
+            0,
+        )?;
         
-        Ok(())
+        // Cancel the pending ownership transfer transaction, if one was passed in.
+        // Any other one is cancelled when someone tries to execute it.
+        if let Some(pending_tx) = ctx.accounts.pending_transaction.as_mut() {
+            require!(
+                pending_tx.transaction_type == TransactionType::TransferOwnership
+                    && pending_tx.status == TransactionStatus::Pending,
+                VaultError::TransactionNotFound
+            );
+            pending_tx.status = TransactionStatus::Cancelled;
+            vault.settle_transaction();
+        }
+        
+        Ok(())
+    }
+
+    /**
+     * 金庫閉鎖命令（closeVault）
+     * 
+     * 金庫に残っているトークンを指定した送金先に全て移動し、金庫のトークンアカウントと
+     * 金庫アカウントを閉鎖して、レント（lamports）を所有者に返却します。
+     * 
+     * 閉鎖には以下の条件が確認されます：
+     * - 呼び出し者が所有者であること
+     * - 金庫が一時停止またはタイムロックされていないこと
+     * - 閉鎖以外の保留中トランザクションが存在しないこと
+     * - 許可リストが有効な場合は、送金先が有効な許可リストに含まれていること
+     * - 残高が引き出し制限と現在の期間の残り引き出し枠を超えていないこと
+     * - 引き出しの待機時間が設定されている単一署名の金庫では、残高が0であること
+     *   （残高は先にrequest_withdrawal命令とexecute_withdrawal命令で引き出します）
+     * - 支払いが残っている権利確定スケジュールが存在しないこと
+     * - 登録済みのミントが存在しないこと（unregister_mint命令で先に登録を解除します）
+     * - 実行待ちの引き出し予約が存在しないこと
+     * - 停止されていない定期支払いが存在しないこと
+     * 
+     * 監査ログを作成した金庫では、監査ログのアカウントも閉鎖してレントを所有者に返却します。
+     * 
+     * 転送手数料付きのミントで手数料が源泉徴収されている場合は、トークンアカウントを閉鎖する前に
+     * 手数料をミントに回収（harvest）しておく必要があります。
+     * 
+     * 多重署名が設定されている場合、最初の呼び出しでは閉鎖の保留中トランザクションが作成されます。
+     * 必要な署名数の承認が集まった後に、その保留中トランザクションを指定して再度呼び出すと、閉鎖が実行されます。
+     * 
+     * @param ctx - 金庫閉鎖に必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn close_vault(ctx: Context<CloseVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        vault.record_owner_activity(Clock::get()?.unix_timestamp as u64);
+        
+        // Check if the vault is paused
+        require!(!vault.paused, VaultError::VaultPaused);
+        
+        // Check if the vault is locked
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        require!(current_timestamp >= vault.lock_until, VaultError::VaultLocked);
+        
+        // Check the destination against the allowlist
+        vault.check_destination(&ctx.accounts.destination_token_account, current_timestamp)?;
+        
+        // Tokens owed to beneficiaries must be released or revoked first
+        require!(vault.reserved_amount == 0, VaultError::VestingSchedulesExist);
+        
+        // Streams need the vault to be cancelled, and would revive on a re-initialized vault
+        require!(vault.open_payment_streams == 0, VaultError::PaymentStreamsExist);
+        
+        // The audit log's rent would otherwise be stuck at its PDA
+        require!(
+            ctx.accounts.audit_log.is_some() || !vault.audit_log_enabled,
+            VaultError::AuditLogRequired
+        );
+        
+        // Token accounts of registered mints would be left behind
+        require!(vault.mints.is_empty(), VaultError::MintsRegistered);
+        
+        // Requests need the vault to be executed or cancelled, and would revive on a re-initialized vault
+        require!(vault.open_withdrawal_requests == 0, VaultError::WithdrawalRequestsExist);
+        
+        // Check if multisig is required (threshold > 1)
+        if vault.multisig_threshold > 1 {
+            if let Some(pending_tx) = ctx.accounts.pending_transaction.as_mut() {
+                // No other transaction may be waiting for approval
+                require!(vault.open_transactions == 0, VaultError::PendingTransactionsExist);
+                let tx_id = vault.open_transaction();
+                
+                // Create pending transaction
+                pending_tx.set_inner(PendingTransaction {
+                    vault: vault.key(),
+                    id: tx_id,
+                    proposer: ctx.accounts.owner.key(),
+                    transaction_type: TransactionType::CloseVault,
+                    amount: 0, // The whole remaining balance is swept
+                    destination: ctx.accounts.destination_token_account.key(),
+                    new_owner: None,
+                    signers: vec![ctx.accounts.owner.key()],
+                    rejections: Vec::new(),
+                    threshold: vault.multisig_threshold,
+                    status: TransactionStatus::Pending,
+                    created_at: current_timestamp,
+                    expires_at: vault.transaction_expiry(current_timestamp),
+                    config_version: vault.config_version,
+                    bump: ctx.bumps.pending_transaction,
+                });
+                emit_transaction_proposed(pending_tx);
+                
+                // Return early, the vault is closed once enough signers approve
+                return Ok(());
+            }
+            
+            let close_tx = ctx.accounts.approved_transaction.as_mut()
+                .ok_or(VaultError::InvalidPendingTransactionAccount)?;
+            require!(
+                close_tx.transaction_type == TransactionType::CloseVault
+                    && close_tx.status == TransactionStatus::Pending,
+                VaultError::TransactionNotFound
+            );
+            require!(!close_tx.is_expired(current_timestamp), VaultError::ProposalExpired);
+            require!(close_tx.config_version == vault.config_version, VaultError::StaleTransaction);
+            require!(
+                close_tx.signers.len() >= close_tx.threshold as usize,
+                VaultError::InsufficientApprovals
+            );
+            require!(
+                close_tx.destination == ctx.accounts.destination_token_account.key(),
+                VaultError::DestinationMismatch
+            );
+            
+            // The close transaction itself must be the only one still open
+            require!(vault.open_transactions == 1, VaultError::PendingTransactionsExist);
+            close_tx.status = TransactionStatus::Executed;
+            vault.settle_transaction();
+        } else {
+            // No transaction may be waiting for approval
+            require!(vault.open_transactions == 0, VaultError::PendingTransactionsExist);
+            require!(
+                ctx.accounts.pending_transaction.is_none() && ctx.accounts.approved_transaction.is_none(),
+                VaultError::InvalidPendingTransactionAccount
+            );
+            
+            // Delayed vaults must empty the vault through announced withdrawals first
+            require!(
+                vault.withdrawal_delay == 0 || ctx.accounts.vault_token_account.amount == 0,
+                VaultError::WithdrawalDelayRequired
+            );
+        }
+        
+        // Sweep any remaining balance to the destination
+        let remaining = ctx.accounts.vault_token_account.amount;
+        if remaining > 0 {
+            // The sweep is bounded like any other withdrawal
+            require!(remaining <= vault.max_withdrawal_limit, VaultError::ExceedsWithdrawalLimit);
+            vault.consume_window_budget(remaining, current_timestamp)?;
+            
+            execute_withdraw(
+                &ctx.accounts.vault,
+                ctx.accounts.vault_token_account.to_account_info(),
+                &ctx.accounts.token_mint,
+                ctx.accounts.destination_token_account.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                remaining,
+            )?;
+            emit!(Withdrawn {
+                vault: ctx.accounts.vault.key(),
+                actor: ctx.accounts.owner.key(),
+                destination: ctx.accounts.destination_token_account.key(),
+                amount: remaining,
+                timestamp: current_timestamp,
+            });
+        }
+        
+        // Close the token account, returning its rent to the owner
+        let creator = ctx.accounts.vault.creator;
+        let vault_id_bytes = ctx.accounts.vault.vault_id.to_le_bytes();
+        let bump = ctx.accounts.vault.bump;
+        let seeds = &[
+            b"vault".as_ref(),
+            creator.as_ref(),
+            vault_id_bytes.as_ref(),
+            &[bump],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = token_interface::CloseAccount {
+            account: ctx.accounts.vault_token_account.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token_interface::close_account(cpi_ctx)?;
+        
+        // The audit log is closed with the vault, so a re-initialized vault can create a new one
+        if let Some(audit_log) = &ctx.accounts.audit_log {
+            audit_log.close(ctx.accounts.owner.to_account_info())?;
+        }
+        
+        // Close the vault account itself, returning its rent to the owner
+        ctx.accounts.vault.close(ctx.accounts.owner.to_account_info())?;
+        
+        Ok(())
+    }
+
+    /**
+     * 金庫サイズ変更命令（resizeVault）
+     * 
+     * 委任者、署名者、許可リストの最大数を変更し、
+     * それに合わせて金庫アカウントのサイズを再割り当て（realloc）します。
+     * 追加で必要なレントは所有者が支払い、縮小時には差額が所有者に返却されます。
+     * 
+     * @param ctx - サイズ変更に必要なアカウント情報を含むコンテキスト
+     * @param max_delegates - 委任者の最大数
+     * @param max_signers - 追加署名者の最大数
+     * @param max_allowlist - 許可リストの最大登録数
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn resize_vault(
+        ctx: Context<ResizeVault>,
+        max_delegates: u16,
+        max_signers: u16,
+        max_allowlist: u16,
+    ) -> Result<()> {
+        // Verify owner
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        vault.record_owner_activity(Clock::get()?.unix_timestamp as u64);
+        
+        // Signer counts are compared against the u8 threshold (+1 for owner)
+        require!(max_signers < u8::MAX as u16, VaultError::InvalidCapacity);
+        
+        // The new capacity must hold everything already stored
+        require!(vault.delegates.len() <= max_delegates as usize, VaultError::InvalidCapacity);
+        require!(vault.multisig_signers.len() <= max_signers as usize, VaultError::InvalidCapacity);
+        require!(vault.allowlist.len() <= max_allowlist as usize, VaultError::InvalidCapacity);
+        
+        vault.max_delegates = max_delegates;
+        vault.max_signers = max_signers;
+        vault.max_allowlist = max_allowlist;
+        
+        Ok(())
+    }
+
+    /**
+     * 保留中トランザクション閉鎖命令（closeTransaction）
+     * 
+     * 実行済み、キャンセル済み、または拒否済みの保留中トランザクションのアカウントを閉鎖し、
+     * レントを提案者に返却します。レントは必ず提案者に返却されるため、誰でも実行できます。
+     * 
+     * @param ctx - 保留中トランザクション閉鎖に必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn close_transaction(ctx: Context<CloseTransaction>) -> Result<()> {
+        // Transactions still waiting for approval cannot be closed
+        require!(
+            ctx.accounts.pending_transaction.status != TransactionStatus::Pending,
+            VaultError::TransactionStillPending
+        );
+        
+        Ok(())
+    }
+
+    /**
+     * 保留中トランザクション有効期間設定命令（setTransactionTtl）
+     * 
+     * これから作成される保留中トランザクションの有効期間を設定します。
+     * 期限を過ぎたトランザクションは承認・実行できなくなり、古い承認が使われることを防ぎます。
+     * 多重署名が設定されている場合は、保留中のトランザクションとして記録されます。
+     * 
+     * @param ctx - 有効期間設定に必要なアカウント情報を含むコンテキスト
+     * @param ttl - 有効期間（秒）。0を指定すると期限なし
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn set_transaction_ttl(ctx: Context<SetTransactionTtl>, ttl: u64) -> Result<()> {
+        // Verify owner
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        
+        // Set the time-to-live for new pending transactions, or propose it if multisig is enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::SetTransactionTtl { ttl },
+        )
+    }
+
+    /**
+     * 期限切れトランザクション整理命令（expireTransaction）
+     * 
+     * 有効期限を過ぎた、または署名者設定の変更前に作成された承認待ちの保留中トランザクションを無効にし、
+     * アカウントを閉鎖してレントを提案者に返却します。誰でも実行できます。
+     * 
+     * @param ctx - 期限切れトランザクション整理に必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn expire_transaction(ctx: Context<ExpireTransaction>) -> Result<()> {
+        let pending_tx = &ctx.accounts.pending_transaction;
+        require!(pending_tx.status == TransactionStatus::Pending, VaultError::TransactionNotFound);
+        
+        // Only transactions past their expiry or made under an older signer set can be cleaned up
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        let is_stale = pending_tx.config_version != ctx.accounts.vault.config_version;
+        require!(pending_tx.is_expired(current_timestamp) || is_stale, VaultError::ProposalNotExpired);
+        
+        // The account is closed on exit, so only the open count needs updating
+        ctx.accounts.vault.settle_transaction();
+        
+        emit!(TransactionExpired {
+            vault: ctx.accounts.vault.key(),
+            tx_id: pending_tx.id,
+            stale: is_stale,
+            timestamp: current_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /**
+     * 承認取り消し命令（revokeApproval）
+     * 
+     * 保留中のトランザクションに対して行った自分の承認を取り消します。
+     * 
+     * @param ctx - 承認取り消しに必要なアカウント情報を含むコンテキスト
+     * @param tx_id - 対象のトランザクションのID
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn revoke_approval(ctx: Context<ManageTransaction>, tx_id: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let pending_tx = &mut ctx.accounts.pending_transaction;
+        let current_signer = ctx.accounts.signer.key();
+        
+        require!(
+            pending_tx.id == tx_id && pending_tx.status == TransactionStatus::Pending,
+            VaultError::TransactionNotFound
+        );
+        if vault.owner == current_signer {
+            vault.record_owner_activity(Clock::get()?.unix_timestamp as u64);
+        }
+        
+        // Remove the approval if it exists
+        let index = pending_tx.signers.iter().position(|&s| s == current_signer)
+            .ok_or(VaultError::ApprovalNotFound)?;
+        pending_tx.signers.remove(index);
+        
+        emit!(ApprovalRevoked {
+            vault: vault.key(),
+            tx_id,
+            signer: current_signer,
+            approvals: pending_tx.signers.len() as u8,
+            timestamp: Clock::get()?.unix_timestamp as u64,
+        });
+        
+        Ok(())
+    }
+
+    /**
+     * トランザクション拒否命令（rejectTransaction）
+     * 
+     * 保留中のトランザクションに反対票を投じます。既に承認していた場合は承認が取り消されます。
+     * 残りの署名者が全員承認しても必要な署名数に届かなくなった時点で、
+     * トランザクションは拒否済みとなり、以後実行できなくなります。
+     * 
+     * @param ctx - トランザクション拒否に必要なアカウント情報を含むコンテキスト
+     * @param tx_id - 拒否するトランザクションのID
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn reject_transaction(ctx: Context<ManageTransaction>, tx_id: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let pending_tx = &mut ctx.accounts.pending_transaction;
+        let current_signer = ctx.accounts.signer.key();
+        
+        // Verify signer is owner or in multisig_signers
+        let is_owner = vault.owner == current_signer;
+        let is_multisig_signer = vault.multisig_signers.contains(&current_signer);
+        
+        require!(is_owner || is_multisig_signer, VaultError::Unauthorized);
+        if is_owner {
+            vault.record_owner_activity(Clock::get()?.unix_timestamp as u64);
+        }
+        
+        require!(
+            pending_tx.id == tx_id && pending_tx.status == TransactionStatus::Pending,
+            VaultError::TransactionNotFound
+        );
+        
+        // Record the rejection, replacing an earlier approval
+        pending_tx.signers.retain(|&s| s != current_signer);
+        if !pending_tx.rejections.contains(&current_signer) {
+            pending_tx.rejections.push(current_signer);
+        }
+        
+        // Reject permanently once the threshold can no longer be reached
+        let eligible_signers = vault.multisig_signers.len() + 1; // +1 for owner
+        let remaining_signers = eligible_signers.saturating_sub(pending_tx.rejections.len());
+        if remaining_signers < pending_tx.threshold as usize {
+            pending_tx.status = TransactionStatus::Rejected;
+            vault.settle_transaction();
+        }
+        
+        emit!(TransactionRejected {
+            vault: vault.key(),
+            tx_id,
+            signer: current_signer,
+            rejections: pending_tx.rejections.len() as u8,
+            rejected: pending_tx.status == TransactionStatus::Rejected,
+            timestamp: Clock::get()?.unix_timestamp as u64,
+        });
+        
+        Ok(())
+    }
+
+    /**
+     * トランザクションキャンセル命令（cancelTransaction）
+     * 
+     * 保留中のトランザクションを取り下げます。提案者または所有者のみが実行できます。
+     * 所有権譲渡のトランザクションはcancel_ownership_transfer命令でキャンセルします。
+     * 
+     * @param ctx - トランザクションキャンセルに必要なアカウント情報を含むコンテキスト
+     * @param tx_id - キャンセルするトランザクションのID
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn cancel_transaction(ctx: Context<ManageTransaction>, tx_id: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let pending_tx = &mut ctx.accounts.pending_transaction;
+        let current_signer = ctx.accounts.signer.key();
+        
+        // Verify signer is the proposer or the owner
+        let is_owner = vault.owner == current_signer;
+        let is_proposer = pending_tx.proposer == current_signer;
+        
+        require!(is_owner || is_proposer, VaultError::Unauthorized);
+        if is_owner {
+            vault.record_owner_activity(Clock::get()?.unix_timestamp as u64);
+        }
+        
+        require!(
+            pending_tx.id == tx_id && pending_tx.status == TransactionStatus::Pending,
+            VaultError::TransactionNotFound
+        );
+        require!(
+            pending_tx.transaction_type != TransactionType::TransferOwnership,
+            VaultError::InvalidTransactionType
+        );
+        
+        pending_tx.status = TransactionStatus::Cancelled;
+        vault.settle_transaction();
+        
+        emit!(TransactionCancelled {
+            vault: vault.key(),
+            tx_id,
+            actor: current_signer,
+            timestamp: Clock::get()?.unix_timestamp as u64,
+        });
+        
+        Ok(())
+    }
+
+    /**
+     * 引き出し待機時間設定命令（setWithdrawalDelay）
+     * 
+     * 引き出しを予約してから実行できるようになるまでの待機時間を設定します。
+     * 0より大きい値を設定すると、単一署名の金庫ではwithdraw命令による即時の引き出しができなくなります。
+     * 多重署名が設定されている場合は、保留中のトランザクションとして記録されます。
+     * 
+     * @param ctx - 引き出し制限設定に必要なアカウント情報を含むコンテキスト
+     * @param delay - 待機時間（秒、0は待機なし）
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn set_withdrawal_delay(ctx: Context<SetWithdrawalLimit>, delay: u64) -> Result<()> {
+        // Verify owner
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        
+        // Set the delay, or propose it if multisig is enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::SetWithdrawalDelay { delay },
+        )
+    }
+
+    /**
+     * 引き出し予約命令（requestWithdrawal）
+     * 
+     * 引き出しの金額と送金先を記録し、待機時間の経過後に実行できる引き出し予約を作成します。
+     * 待機時間中は所有者、ガーディアン、または多重署名の署名者が予約をキャンセルできます。
+     * 
+     * 予約時には以下の条件が確認されます：
+     * - 呼び出し者が所有者または委任された権限を持っていること
+     * - 送金先が呼び出し者のトークンアカウントであること
+     * - 引き出し金額が制限を超えていないこと
+     * - 許可リストが有効な場合は、送金先が有効な許可リストに含まれていること
+     * 
+     * 引き出し金額に複数の署名が必要な場合は、保留中トランザクションで承認するため使用できません。
+     * 主要ミント以外の登録済みミントは所有者のみが予約でき、多重署名の閾値が適用されます。
+     * 
+     * @param ctx - 引き出し予約に必要なアカウント情報を含むコンテキスト
+     * @param amount - 引き出すトークンの量
+     * @param mint - 引き出すトークンのミントアドレス（登録済みのミントであること）
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn request_withdrawal(ctx: Context<RequestWithdrawal>, amount: u64, mint: Pubkey) -> Result<()> {
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        
+        // Verify owner or delegate
+        let caller = ctx.accounts.requester.key();
+        let vault = &mut ctx.accounts.vault;
+        let is_primary = mint == vault.mint;
+        if vault.owner == caller {
+            vault.record_owner_activity(current_timestamp);
+        } else {
+            let record = vault.delegates.iter()
+                .find(|record| record.delegate == caller)
+                .ok_or(VaultError::Unauthorized)?;
+            
+            // Delegate allowances are denominated in the primary mint
+            require!(is_primary, VaultError::DelegatePermissionDenied);
+            
+            // The allowance is spent once the request is executed
+            record.authorize(DELEGATE_PERMISSION_WITHDRAW, current_timestamp)?;
+            require!(
+                amount <= record.remaining_allowance(current_timestamp),
+                VaultError::ExceedsDelegateAllowance
+            );
+        }
+        
+        // Multisig vaults approve withdrawals through pending transactions.
+        // Approval tiers are denominated in the primary mint.
+        let threshold = if is_primary {
+            vault.withdrawal_threshold(amount)
+        } else {
+            vault.multisig_threshold
+        };
+        require!(threshold <= 1, VaultError::MultisigApprovalRequired);
+        
+        // Check if the vault is paused
+        require!(!vault.paused, VaultError::VaultPaused);
+        
+        // Check withdrawal limit
+        require!(amount <= vault.withdrawal_limit(&mint), VaultError::ExceedsWithdrawalLimit);
+        
+        // Check the destination against the allowlist
+        vault.check_destination(&ctx.accounts.destination_token_account, current_timestamp)?;
+        
+        let request_id = vault.open_withdrawal_request();
+        
+        ctx.accounts.withdrawal_request.set_inner(WithdrawalRequest {
+            vault: vault.key(),
+            id: request_id,
+            requester: caller,
+            amount,
+            destination: ctx.accounts.destination_token_account.key(),
+            requested_at: current_timestamp,
+            executable_at: current_timestamp.saturating_add(vault.withdrawal_delay),
+            bump: ctx.bumps.withdrawal_request,
+        });
+        
+        emit!(WithdrawalRequested {
+            vault: vault.key(),
+            request_id,
+            requester: caller,
+            destination: ctx.accounts.destination_token_account.key(),
+            amount,
+            executable_at: ctx.accounts.withdrawal_request.executable_at,
+            timestamp: current_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /**
+     * 引き出し予約実行命令（executeWithdrawal）
+     * 
+     * 待機時間が経過した引き出し予約を実行し、予約のアカウントを閉鎖してレントを予約者に返却します。
+     * 送金先は予約時に記録されたものに限られるため、誰でも実行できます。
+     * 待機時間中に予約者が所有者でなくなった場合や、委任者の引き出し権限が失われた場合は実行できません。
+     * 委任者の予約では、委任者の引き出し枠は予約時ではなく実行時に差し引かれます。
+     * 予約枠と期間ごとの引き出し上限は主要ミントの引き出しにのみ適用されます。
+     * 
+     * @param ctx - 引き出し予約実行に必要なアカウント情報を含むコンテキスト
+     * @param mint - 予約時に指定したトークンのミントアドレス
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>, mint: Pubkey) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let request = &ctx.accounts.withdrawal_request;
+        let is_primary = mint == vault.mint;
+        
+        // Check if the delay has passed
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        require!(current_timestamp >= request.executable_at, VaultError::WithdrawalNotReady);
+        
+        // The requester may have been removed or replaced as owner during the delay.
+        // Delegates spend down their own allowance now that the withdrawal is carried out.
+        if vault.owner != request.requester {
+            vault.spend_delegate_allowance(request.requester, request.amount, current_timestamp)?;
+        }
+        
+        // Check if the vault is paused
+        require!(!vault.paused, VaultError::VaultPaused);
+        
+        // Check if the vault is locked
+        require!(current_timestamp >= vault.lock_until, VaultError::VaultLocked);
+        
+        // The allowlist may have changed during the delay
+        vault.check_destination(&ctx.accounts.destination_token_account, current_timestamp)?;
+        
+        // Charge the rolling window budget.
+        // Reservations and the window are denominated in the primary mint.
+        let amount = request.amount;
+        if is_primary {
+            vault.check_unreserved(ctx.accounts.vault_token_account.amount, amount)?;
+            vault.consume_window_budget(amount, current_timestamp)?;
+        }
+        
+        // The request account is closed on exit, so only the open count needs updating
+        vault.settle_withdrawal_request();
+        
+        execute_withdraw(
+            &ctx.accounts.vault,
+            ctx.accounts.vault_token_account.to_account_info(),
+            &ctx.accounts.token_mint,
+            ctx.accounts.destination_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            amount,
+        )?;
+        
+        emit!(Withdrawn {
+            vault: ctx.accounts.vault.key(),
+            actor: ctx.accounts.withdrawal_request.requester,
+            destination: ctx.accounts.destination_token_account.key(),
+            amount,
+            timestamp: current_timestamp,
+        });
+        record_audit_entry(
+            &ctx.accounts.vault,
+            &ctx.accounts.audit_log,
+            AUDIT_ACTION_WITHDRAW,
+            ctx.accounts.withdrawal_request.requester,
+            ctx.accounts.destination_token_account.key(),
+            amount,
+        )?;
+        
+        Ok(())
+    }
+
+    /**
+     * 引き出し予約キャンセル命令（cancelWithdrawal）
+     * 
+     * 待機時間中の引き出し予約を取り消し、予約のアカウントを閉鎖してレントを予約者に返却します。
+     * 所有者、予約者、ガーディアン、または多重署名の署名者が実行できます。
+     * 
+     * @param ctx - 引き出し予約キャンセルに必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let current_signer = ctx.accounts.signer.key();
+        
+        // Verify signer is the owner, the requester, a guardian or a multisig signer
+        let is_owner = vault.owner == current_signer;
+        let is_requester = ctx.accounts.withdrawal_request.requester == current_signer;
+        let is_guardian = vault.guardians.contains(&current_signer);
+        let is_signer = vault.multisig_signers.contains(&current_signer);
+        
+        require!(is_owner || is_requester || is_guardian || is_signer, VaultError::Unauthorized);
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        if is_owner {
+            vault.record_owner_activity(current_timestamp);
+        }
+        
+        // The request account is closed on exit, so only the open count needs updating
+        vault.settle_withdrawal_request();
+        
+        emit!(WithdrawalCancelled {
+            vault: vault.key(),
+            request_id: ctx.accounts.withdrawal_request.id,
+            actor: current_signer,
+            amount: ctx.accounts.withdrawal_request.amount,
+            timestamp: current_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /**
+     * 権利確定スケジュール作成命令（createVestingSchedule）
+     * 
+     * 受益者に対して、開始時刻から終了時刻まで線形に権利確定するスケジュールを作成します。
+     * クリフ時刻より前には何も確定せず、クリフ時刻以降は開始時刻からの経過時間に応じて確定します。
+     * スケジュールの総額は金庫内に確保され、所有者の引き出しには使用できなくなります。
+     * 
+     * スケジュールの作成は総額の引き出しと同じように扱われ、以下の条件が確認されます：
+     * - 金庫が一時停止またはタイムロックされていないこと
+     * - 総額が引き出し制限と現在の期間の残り引き出し枠を超えていないこと
+     * - 許可リストが有効な場合は、受益者のトークンアカウントが有効な許可リストに含まれていること
+     * - 開始時刻が現在時刻以降であること
+     * - 引き出しの待機時間が設定されている場合は、取り消し可能で、開始時刻が待機時間の経過後であること
+     * 
+     * 総額の引き出しに複数の署名が必要な場合は使用できません。
+     * 
+     * @param ctx - 権利確定スケジュール作成に必要なアカウント情報を含むコンテキスト
+     * @param beneficiary - 受益者のアドレス
+     * @param start - 権利確定の開始UNIXタイムスタンプ
+     * @param cliff - クリフのUNIXタイムスタンプ（開始時刻以上、終了時刻以下）
+     * @param end - 全額が確定するUNIXタイムスタンプ
+     * @param total_amount - 権利確定するトークンの総額
+     * @param revocable - 所有者が未確定分を取り消せるかどうか
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn create_vesting_schedule(
+        ctx: Context<CreateVestingSchedule>,
+        beneficiary: Pubkey,
+        start: u64,
+        cliff: u64,
+        end: u64,
+        total_amount: u64,
+        revocable: bool,
+    ) -> Result<()> {
+        // Verify owner
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        vault.record_owner_activity(current_timestamp);
+        
+        // Committing tokens is as sensitive as withdrawing them
+        require!(vault.withdrawal_threshold(total_amount) <= 1, VaultError::MultisigApprovalRequired);
+        require!(!vault.paused, VaultError::VaultPaused);
+        require!(current_timestamp >= vault.lock_until, VaultError::VaultLocked);
+        require!(total_amount <= vault.max_withdrawal_limit, VaultError::ExceedsWithdrawalLimit);
+        vault.check_destination(&ctx.accounts.beneficiary_token_account, current_timestamp)?;
+        
+        // Validate schedule, which cannot vest retroactively
+        require!(
+            total_amount > 0 && current_timestamp <= start && start <= cliff && cliff <= end && start < end,
+            VaultError::InvalidVestingSchedule
+        );
+        
+        // On delayed vaults nothing vests before the delay passes, and the owner can revoke until then
+        if vault.withdrawal_delay > 0 {
+            require!(
+                revocable && start >= current_timestamp.saturating_add(vault.withdrawal_delay),
+                VaultError::WithdrawalDelayRequired
+            );
+        }
+        
+        // Reserve the tokens in the vault and charge the rolling window budget
+        vault.check_unreserved(ctx.accounts.vault_token_account.amount, total_amount)?;
+        vault.consume_window_budget(total_amount, current_timestamp)?;
+        vault.reserved_amount += total_amount;
+        
+        let schedule_id = vault.vesting_schedule_count;
+        vault.vesting_schedule_count += 1;
+        
+        ctx.accounts.vesting_schedule.set_inner(VestingSchedule {
+            vault: vault.key(),
+            id: schedule_id,
+            beneficiary,
+            funder: ctx.accounts.owner.key(),
+            start,
+            cliff,
+            end,
+            total_amount,
+            released_amount: 0,
+            revocable,
+            revoked: false,
+            bump: ctx.bumps.vesting_schedule,
+        });
+        
+        emit!(VestingScheduleCreated {
+            vault: vault.key(),
+            schedule_id,
+            beneficiary,
+            start,
+            cliff,
+            end,
+            total_amount,
+            revocable,
+            timestamp: current_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /**
+     * 権利確定分支払い命令（releaseVested）
+     * 
+     * 現在時刻までに権利確定した金額のうち、未払いの分を受益者のトークンアカウントに送金します。
+     * 送金先は受益者のトークンアカウントに限られるため、誰でも実行できます。
+     * 引き出し制限はスケジュールの作成時に確認されるため、タイムロックや引き出し制限の影響は受けません。
+     * 金庫が一時停止されている間や、受益者のトークンアカウントが有効な許可リストに含まれていない場合は実行できません。
+     * 
+     * @param ctx - 権利確定分支払いに必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn release_vested(ctx: Context<ReleaseVested>) -> Result<()> {
+        // Check if the vault is paused
+        require!(!ctx.accounts.vault.paused, VaultError::VaultPaused);
+        
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        
+        // The allowlist may have changed since the schedule was created
+        ctx.accounts.vault.check_destination(&ctx.accounts.beneficiary_token_account, current_timestamp)?;
+        
+        let schedule = &mut ctx.accounts.vesting_schedule;
+        
+        let releasable = schedule.vested_amount(current_timestamp) - schedule.released_amount;
+        require!(releasable > 0, VaultError::NothingToRelease);
+        
+        schedule.released_amount += releasable;
+        ctx.accounts.vault.reserved_amount -= releasable;
+        
+        execute_withdraw(
+            &ctx.accounts.vault,
+            ctx.accounts.vault_token_account.to_account_info(),
+            &ctx.accounts.token_mint,
+            ctx.accounts.beneficiary_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            releasable,
+        )?;
+        
+        emit!(Withdrawn {
+            vault: ctx.accounts.vault.key(),
+            actor: ctx.accounts.vesting_schedule.beneficiary,
+            destination: ctx.accounts.beneficiary_token_account.key(),
+            amount: releasable,
+            timestamp: current_timestamp,
+        });
+        record_audit_entry(
+            &ctx.accounts.vault,
+            &ctx.accounts.audit_log,
+            AUDIT_ACTION_WITHDRAW,
+            ctx.accounts.vesting_schedule.beneficiary,
+            ctx.accounts.beneficiary_token_account.key(),
+            releasable,
+        )?;
+        
+        Ok(())
+    }
+
+    /**
+     * 権利確定スケジュール取り消し命令（revokeVesting）
+     * 
+     * 取り消し可能なスケジュールについて、未確定の金額を取り消して所有者の引き出し可能な残高に戻します。
+     * 取り消し時点で確定済みの金額は、引き続き受益者が受け取ることができます。
+     * 
+     * @param ctx - 権利確定スケジュール取り消しに必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn revoke_vesting(ctx: Context<RevokeVesting>) -> Result<()> {
+        // Verify owner
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        vault.record_owner_activity(Clock::get()?.unix_timestamp as u64);
+        
+        let schedule = &mut ctx.accounts.vesting_schedule;
+        require!(schedule.revocable && !schedule.revoked, VaultError::VestingNotRevocable);
+        
+        // Freeze the schedule at what has vested so far
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        let vested = schedule.vested_amount(current_timestamp);
+        let unvested = schedule.total_amount - vested;
+        vault.reserved_amount -= unvested;
+        schedule.total_amount = vested;
+        schedule.revoked = true;
+        
+        emit!(VestingRevoked {
+            vault: vault.key(),
+            schedule_id: schedule.id,
+            actor: ctx.accounts.owner.key(),
+            unvested_amount: unvested,
+            timestamp: current_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /**
+     * 権利確定スケジュール閉鎖命令（closeVestingSchedule）
+     * 
+     * 全額の支払いが完了したスケジュールのアカウントを閉鎖し、レントを作成者に返却します。
+     * レントは必ず作成者に返却されるため、誰でも実行できます。
+     * 
+     * @param ctx - 権利確定スケジュール閉鎖に必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn close_vesting_schedule(ctx: Context<CloseVestingSchedule>) -> Result<()> {
+        let schedule = &ctx.accounts.vesting_schedule;
+        require!(
+            schedule.released_amount == schedule.total_amount,
+            VaultError::VestingNotComplete
+        );
+        
+        Ok(())
+    }
+
+    /**
+     * 定期支払い作成命令（createPaymentStream）
+     * 
+     * 指定したトークンアカウントに、一定期間ごとに一定額を支払う定期支払いを作成します。
+     * 支払いはclaim_stream命令で誰でも実行でき、前回の支払い以降に経過した期間分が送金されます。
+     * 
+     * 過去にさかのぼって支払いを発生させないため、開始時刻は現在時刻以降である必要があります。
+     * 引き出しの待機時間が設定されている場合は、開始時刻が待機時間の経過後である必要があり、
+     * その間に所有者はcancel_payment_stream命令で定期支払いを停止できます。
+     * 
+     * 1期間分の支払いに複数の署名が必要な場合は使用できません。
+     * 
+     * @param ctx - 定期支払い作成に必要なアカウント情報を含むコンテキスト
+     * @param amount_per_period - 1期間あたりの支払額
+     * @param period_length - 期間の長さ（秒）
+     * @param start - 支払いの開始UNIXタイムスタンプ
+     * @param end - 支払いの終了UNIXタイムスタンプ（0は終了なし）
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn create_payment_stream(
+        ctx: Context<CreatePaymentStream>,
+        amount_per_period: u64,
+        period_length: u64,
+        start: u64,
+        end: u64,
+    ) -> Result<()> {
+        // Verify owner
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        vault.record_owner_activity(current_timestamp);
+        
+        // Each payment is a pre-approved withdrawal
+        require!(vault.withdrawal_threshold(amount_per_period) <= 1, VaultError::MultisigApprovalRequired);
+        
+        // Validate stream, which cannot accrue retroactively
+        require!(
+            amount_per_period > 0 && period_length > 0 && current_timestamp <= start && (end == 0 || start < end),
+            VaultError::InvalidPaymentStream
+        );
+        
+        // On delayed vaults nothing accrues before the delay passes, so the stream can be cancelled first
+        require!(
+            start >= current_timestamp.saturating_add(vault.withdrawal_delay),
+            VaultError::WithdrawalDelayRequired
+        );
+        
+        // Check the recipient against the allowlist
+        vault.check_destination(&ctx.accounts.recipient_token_account, current_timestamp)?;
+        
+        let stream_id = vault.payment_stream_count;
+        vault.payment_stream_count += 1;
+        vault.open_payment_streams += 1;
+        
+        ctx.accounts.payment_stream.set_inner(PaymentStream {
+            vault: vault.key(),
+            id: stream_id,
+            recipient: ctx.accounts.recipient_token_account.key(),
+            funder: ctx.accounts.owner.key(),
+            amount_per_period,
+            period_length,
+            start,
+            end,
+            last_claimed: start,
+            bump: ctx.bumps.payment_stream,
+        });
+        
+        emit!(PaymentStreamCreated {
+            vault: vault.key(),
+            stream_id,
+            recipient: ctx.accounts.recipient_token_account.key(),
+            amount_per_period,
+            period_length,
+            start,
+            end,
+            timestamp: current_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /**
+     * 定期支払い実行命令（claimStream）
+     * 
+     * 前回の支払い以降に経過した期間分の支払額を受取人のトークンアカウントに送金します。
+     * 送金先は定期支払いに記録されたものに限られるため、誰でも実行できます。
+     * 引き出し制限や期間ごとの引き出し枠に収まらない期間分は支払われずに残り、次回以降に支払われます。
+     * 
+     * 支払いには通常の引き出しと同じく以下の条件が確認されます：
+     * - 金庫が一時停止またはタイムロックされていないこと
+     * - 少なくとも1期間分の支払額が引き出し制限と期間ごとの引き出し枠に収まること
+     * - 許可リストが有効な場合は、送金先が有効な許可リストに含まれていること
+     * - 権利確定スケジュールに確保された金額に手を付けないこと
+     * 
+     * @param ctx - 定期支払い実行に必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn claim_stream(ctx: Context<ClaimStream>) -> Result<()> {
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        let vault = &mut ctx.accounts.vault;
+        let stream = &mut ctx.accounts.payment_stream;
+        
+        // Only whole periods are paid
+        let accrued = stream.accrued_periods(current_timestamp);
+        require!(accrued > 0, VaultError::NothingAccrued);
+        
+        // Check if the vault is paused
+        require!(!vault.paused, VaultError::VaultPaused);
+        
+        // Check if the vault is locked
+        require!(current_timestamp >= vault.lock_until, VaultError::VaultLocked);
+        
+        // Pay as many periods as the withdrawal limits allow, the rest stays claimable
+        let per_call = vault.max_withdrawal_limit / stream.amount_per_period;
+        require!(per_call > 0, VaultError::ExceedsWithdrawalLimit);
+        let per_window = vault.remaining_window_budget(current_timestamp) / stream.amount_per_period;
+        require!(per_window > 0, VaultError::ExceedsWindowBudget);
+        let periods = accrued.min(per_call).min(per_window);
+        let amount = periods * stream.amount_per_period;
+        
+        vault.check_destination(&ctx.accounts.recipient_token_account, current_timestamp)?;
+        vault.check_unreserved(ctx.accounts.vault_token_account.amount, amount)?;
+        vault.consume_window_budget(amount, current_timestamp)?;
+        
+        stream.last_claimed += periods * stream.period_length;
+        
+        execute_withdraw(
+            &ctx.accounts.vault,
+            ctx.accounts.vault_token_account.to_account_info(),
+            &ctx.accounts.token_mint,
+            ctx.accounts.recipient_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            amount,
+        )?;
+        
+        emit!(Withdrawn {
+            vault: ctx.accounts.vault.key(),
+            actor: ctx.accounts.payment_stream.funder,
+            destination: ctx.accounts.recipient_token_account.key(),
+            amount,
+            timestamp: current_timestamp,
+        });
+        record_audit_entry(
+            &ctx.accounts.vault,
+            &ctx.accounts.audit_log,
+            AUDIT_ACTION_WITHDRAW,
+            ctx.accounts.payment_stream.funder,
+            ctx.accounts.recipient_token_account.key(),
+            amount,
+        )?;
+        
+        Ok(())
+    }
+
+    /**
+     * 定期支払い停止命令（cancelPaymentStream）
+     * 
+     * 定期支払いを停止し、アカウントを閉鎖してレントを作成者に返却します。
+     * 未払いの期間分は支払われないため、必要に応じて先にclaim_stream命令を実行してください。
+     * 
+     * @param ctx - 定期支払い停止に必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn cancel_payment_stream(ctx: Context<CancelPaymentStream>) -> Result<()> {
+        // Verify owner
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        vault.record_owner_activity(Clock::get()?.unix_timestamp as u64);
+        
+        // The stream account is closed on exit, so only the open count needs updating
+        vault.open_payment_streams = vault.open_payment_streams.saturating_sub(1);
+        
+        emit!(PaymentStreamCancelled {
+            vault: vault.key(),
+            stream_id: ctx.accounts.payment_stream.id,
+            actor: ctx.accounts.owner.key(),
+            timestamp: vault.last_owner_activity,
+        });
+        
+        Ok(())
+    }
+
+    /**
+     * ガーディアン設定命令（setGuardians）
+     * 
+     * 緊急時に金庫を一時停止できるガーディアンのリストを設定します。
+     * ガーディアンは資金を移動できず、一時停止と引き出し予約のキャンセルのみを行えます。
+     * 多重署名が設定されている場合は、保留中のトランザクションとして記録されます。
+     * 
+     * @param ctx - ガーディアン管理に必要なアカウント情報を含むコンテキスト
+     * @param guardians - ガーディアンのアドレスのリスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn set_guardians(ctx: Context<ManageGuardians>, guardians: Vec<Pubkey>) -> Result<()> {
+        // Verify owner
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        
+        // Set the guardians, or propose them if multisig is enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::SetGuardians { guardians },
+        )
+    }
+
+    /**
+     * 一時停止命令（pause）
+     * 
+     * 金庫を即時に一時停止します。ガーディアンまたは所有者が実行できます。
+     * 一時停止中は引き出し、承認済みの引き出しの実行、引き出し予約、権利確定分の支払い、
+     * 定期支払い、金庫の閉鎖など、資金が移動する全ての操作が停止されます。
+     * 
+     * @param ctx - 一時停止に必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn pause(ctx: Context<Pause>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let current_signer = ctx.accounts.signer.key();
+        
+        // Verify signer is a guardian or the owner
+        let is_owner = vault.owner == current_signer;
+        let is_guardian = vault.guardians.contains(&current_signer);
+        
+        require!(is_owner || is_guardian, VaultError::Unauthorized);
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        if is_owner {
+            vault.record_owner_activity(current_timestamp);
+        }
+        
+        vault.paused = true;
+        
+        emit!(Paused {
+            vault: vault.key(),
+            actor: current_signer,
+            timestamp: current_timestamp,
+        });
+        record_audit_entry(
+            vault,
+            &ctx.accounts.audit_log,
+            AUDIT_ACTION_PAUSE,
+            current_signer,
+            Pubkey::default(),
+            0,
+        )?;
+        
+        Ok(())
+    }
+
+    /**
+     * 一時停止解除命令（unpause）
+     * 
+     * 金庫の一時停止を解除します。所有者のみが実行でき、ガーディアンは解除できません。
+     * 多重署名が設定されている場合は、保留中のトランザクションとして記録されます。
+     * 
+     * @param ctx - ガーディアン管理に必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn unpause(ctx: Context<ManageGuardians>) -> Result<()> {
+        // Verify owner
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        
+        // Resume the vault, or propose it if multisig is enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::Unpause,
+        )
+    }
+
+    /**
+     * リカバリー設定命令（setRecovery）
+     * 
+     * 所有者の鍵を紛失した場合に、ガーディアンが新しい所有者を指定できるリカバリーを設定します。
+     * 必要な承認数を0にするとリカバリーは無効になります。
+     * 多重署名が設定されている場合は、保留中のトランザクションとして記録されます。
+     * 
+     * @param ctx - ガーディアン管理に必要なアカウント情報を含むコンテキスト
+     * @param threshold - リカバリーに必要なガーディアンの承認数（0は無効）
+     * @param delay - 承認が集まってからリカバリーを確定できるまでの待機時間（秒）
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn set_recovery(ctx: Context<ManageGuardians>, threshold: u8, delay: u64) -> Result<()> {
+        // Verify owner
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        
+        // Configure recovery, or propose it if multisig is enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::SetRecovery { threshold, delay },
+        )
+    }
+
+    /**
+     * リカバリー提案命令（proposeRecovery）
+     * 
+     * ガーディアンが新しい所有者を提案し、リカバリーを開始します。
+     * 提案したガーディアンの承認は自動的に記録されます。
+     * 
+     * @param ctx - リカバリーに必要なアカウント情報を含むコンテキスト
+     * @param new_owner - 新しい所有者のアドレス
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn propose_recovery(ctx: Context<Recovery>, new_owner: Pubkey) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let guardian = ctx.accounts.signer.key();
+        
+        // Verify guardian
+        require!(vault.recovery_threshold > 0, VaultError::RecoveryNotConfigured);
+        require!(vault.guardians.contains(&guardian), VaultError::Unauthorized);
+        
+        // Only one recovery can be in flight
+        require!(vault.pending_recovery.is_none(), VaultError::RecoveryInProgress);
+        
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        let mut recovery = PendingRecovery {
+            new_owner,
+            approvals: vec![guardian],
+            executable_at: 0,
+        };
+        recovery.start_delay_if_approved(vault.recovery_threshold, vault.recovery_delay, current_timestamp);
+        
+        emit!(RecoveryProposed {
+            vault: vault.key(),
+            guardian,
+            new_owner,
+            executable_at: recovery.executable_at,
+            timestamp: current_timestamp,
+        });
+        vault.pending_recovery = Some(recovery);
+        
+        Ok(())
+    }
+
+    /**
+     * リカバリー承認命令（approveRecovery）
+     * 
+     * ガーディアンが進行中のリカバリーを承認します。
+     * 必要な承認数が集まると待機時間が始まり、経過後にfinalize_recovery命令で確定できます。
+     * 
+     * @param ctx - リカバリーに必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn approve_recovery(ctx: Context<Recovery>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let guardian = ctx.accounts.signer.key();
+        
+        // Verify guardian
+        require!(vault.guardians.contains(&guardian), VaultError::Unauthorized);
+        
+        let threshold = vault.recovery_threshold;
+        let delay = vault.recovery_delay;
+        let recovery = vault.pending_recovery.as_mut()
+            .ok_or(VaultError::NoRecoveryPending)?;
+        
+        // Add the approval if not already approved
+        if !recovery.approvals.contains(&guardian) {
+            recovery.approvals.push(guardian);
+        }
+        
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        recovery.start_delay_if_approved(threshold, delay, current_timestamp);
+        
+        let approvals = recovery.approvals.len() as u8;
+        let executable_at = recovery.executable_at;
+        emit!(RecoveryApproved {
+            vault: vault.key(),
+            guardian,
+            approvals,
+            executable_at,
+            timestamp: current_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /**
+     * リカバリー拒否命令（vetoRecovery）
+     * 
+     * 所有者が進行中のリカバリーを取り消します。
+     * 所有者の鍵が失われていない場合、待機時間中にこの命令で不正なリカバリーを防ぐことができます。
+     * 
+     * @param ctx - リカバリーに必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn veto_recovery(ctx: Context<Recovery>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        
+        // Verify owner
+        require!(vault.owner == ctx.accounts.signer.key(), VaultError::Unauthorized);
+        let recovery = vault.pending_recovery.take()
+            .ok_or(VaultError::NoRecoveryPending)?;
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        vault.record_owner_activity(current_timestamp);
+        
+        emit!(RecoveryVetoed {
+            vault: vault.key(),
+            owner: vault.owner,
+            new_owner: recovery.new_owner,
+            timestamp: current_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /**
+     * リカバリー確定命令（finalizeRecovery）
+     * 
+     * 必要な承認が集まり、待機時間が経過したリカバリーを確定します。誰でも実行できます。
+     * 所有者を新しいアドレスに変更し、委任者を削除します。
+     * 署名者設定のバージョンを更新するため、承認待ちの保留中トランザクションは全て無効になり、
+     * expire_transaction命令で閉鎖できるようになります。
+     * 
+     * @param ctx - リカバリーに必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn finalize_recovery(ctx: Context<Recovery>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        let recovery = vault.pending_recovery.as_ref()
+            .ok_or(VaultError::NoRecoveryPending)?;
+        
+        // Check if the delay has passed
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        require!(
+            recovery.executable_at != 0 && current_timestamp >= recovery.executable_at,
+            VaultError::RecoveryNotReady
+        );
+        
+        let new_owner = recovery.new_owner;
+        emit!(RecoveryFinalized {
+            vault: vault.key(),
+            previous_owner: vault.owner,
+            new_owner,
+            approvals: recovery.approvals.len() as u8,
+            timestamp: current_timestamp,
+        });
+        emit!(OwnershipTransferred {
+            vault: vault.key(),
+            previous_owner: vault.owner,
+            new_owner,
+            timestamp: current_timestamp,
+        });
+        record_audit_entry(
+            vault,
+            &ctx.accounts.audit_log,
+            AUDIT_ACTION_OWNERSHIP_TRANSFERRED,
+            vault.owner,
+            new_owner,
+            0,
+        )?;
+        vault.rotate_owner(new_owner, current_timestamp);
+        
+        Ok(())
+    }
+
+    /**
+     * 相続設定命令（setInheritance）
+     * 
+     * 所有者が一定期間署名した操作を行わなかった場合に、金庫を請求できる受益者を設定します。
+     * 多重署名が設定されている場合は、保留中のトランザクションとして記録されます。
+     * 
+     * @param ctx - ガーディアン管理に必要なアカウント情報を含むコンテキスト
+     * @param beneficiary - 受益者のアドレス（Noneは相続なし）
+     * @param inactivity_period - 相続を請求できるまでの無活動期間（秒、0は無効）
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn set_inheritance(
+        ctx: Context<ManageGuardians>,
+        beneficiary: Option<Pubkey>,
+        inactivity_period: u64,
+    ) -> Result<()> {
+        // Verify owner
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        
+        // Configure inheritance, or propose it if multisig is enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::SetInheritance { beneficiary, inactivity_period },
+        )
+    }
+
+    /**
+     * 生存確認命令（heartbeat）
+     * 
+     * 所有者の活動を記録し、相続のタイマーをリセットします。
+     * 所有者が署名する他の操作でもタイマーはリセットされます。
+     * 
+     * @param ctx - 生存確認に必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn heartbeat(ctx: Context<Heartbeat>) -> Result<()> {
+        // Verify owner
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        vault.record_owner_activity(Clock::get()?.unix_timestamp as u64);
+        
+        Ok(())
+    }
+
+    /**
+     * 相続請求命令（claimInheritance）
+     * 
+     * 所有者が設定された期間活動していない場合に、受益者が金庫を請求します。
+     * 受益者は所有権を引き継ぐか、権利確定スケジュールに確保された分を除く残高を受け取るかを選択できます。
+     * 所有権を引き継ぐ場合は委任者が削除され、承認待ちの保留中トランザクションは全て無効になります。
+     * 
+     * @param ctx - 相続請求に必要なアカウント情報を含むコンテキスト
+     * @param claim - 請求の方法
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn claim_inheritance(ctx: Context<ClaimInheritance>, claim: InheritanceClaim) -> Result<()> {
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        let vault = &mut ctx.accounts.vault;
+        let beneficiary = ctx.accounts.beneficiary.key();
+        
+        // Verify beneficiary and inactivity
+        require!(vault.beneficiary == Some(beneficiary), VaultError::Unauthorized);
+        require!(vault.is_inheritable(current_timestamp), VaultError::OwnerStillActive);
+        
+        match claim {
+            InheritanceClaim::TakeOwnership => {
+                emit!(OwnershipTransferred {
+                    vault: vault.key(),
+                    previous_owner: vault.owner,
+                    new_owner: beneficiary,
+                    timestamp: current_timestamp,
+                });
+                record_audit_entry(
+                    vault,
+                    &ctx.accounts.audit_log,
+                    AUDIT_ACTION_OWNERSHIP_TRANSFERRED,
+                    vault.owner,
+                    beneficiary,
+                    0,
+                )?;
+                vault.rotate_owner(beneficiary, current_timestamp);
+                vault.beneficiary = None;
+            },
+            InheritanceClaim::Sweep => {
+                // Check if the vault is paused
+                require!(!vault.paused, VaultError::VaultPaused);
+                
+                // Check if the vault is locked
+                require!(current_timestamp >= vault.lock_until, VaultError::VaultLocked);
+                
+                // The destination must belong to the beneficiary
+                let destination = ctx.accounts.beneficiary_token_account.as_ref()
+                    .ok_or(VaultError::DestinationMismatch)?;
+                require!(destination.owner == beneficiary, VaultError::DestinationMismatch);
+                require!(destination.mint == ctx.accounts.vault_token_account.mint, VaultError::InvalidMint);
+                
+                // Tokens reserved for vesting stay in the vault
+                let amount = ctx.accounts.vault_token_account.amount.saturating_sub(vault.reserved_amount);
+                if amount > 0 {
+                    execute_withdraw(
+                        &ctx.accounts.vault,
+                        ctx.accounts.vault_token_account.to_account_info(),
+                        &ctx.accounts.token_mint,
+                        destination.to_account_info(),
+                        ctx.accounts.token_program.to_account_info(),
+                        amount,
+                    )?;
+                    
+                    emit!(Withdrawn {
+                        vault: ctx.accounts.vault.key(),
+                        actor: beneficiary,
+                        destination: destination.key(),
+                        amount,
+                        timestamp: current_timestamp,
+                    });
+                    record_audit_entry(
+                        &ctx.accounts.vault,
+                        &ctx.accounts.audit_log,
+                        AUDIT_ACTION_WITHDRAW,
+                        beneficiary,
+                        destination.key(),
+                        amount,
+                    )?;
+                }
+            },
+        }
+        
+        Ok(())
+    }
+
+    /**
+     * 監査ログ作成命令（initAuditLog）
+     * 
+     * 金庫の監査ログ（固定サイズのリングバッファ）を作成します。
+     * 作成後は、預け入れ、引き出し、設定変更、所有権の変更を行う命令に監査ログのアカウントが必要になります。
+     * 監査ログは削除できません。
+     * 
+     * @param ctx - 監査ログ作成に必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn init_audit_log(ctx: Context<InitAuditLog>) -> Result<()> {
+        // Verify owner
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        vault.record_owner_activity(Clock::get()?.unix_timestamp as u64);
+        
+        let mut audit_log = ctx.accounts.audit_log.load_init()?;
+        audit_log.vault = vault.key();
+        audit_log.head = 0;
+        vault.audit_log_enabled = true;
+        
+        Ok(())
+    }
+
+    /**
+     * 監査ログ読み取り命令（readAuditLog）
+     * 
+     * 監査ログに保持されているエントリを古い順に返します。
+     * これは読み取り専用の操作で、ブロックチェーンの状態を変更しません。
+     * 
+     * @param ctx - 監査ログ読み取りに必要なアカウント情報を含むコンテキスト
+     * @param offset - 保持されている最も古いエントリからの位置
+     * @param limit - 返すエントリの最大数（最大MAX_AUDIT_LOG_READ）
+     * @return Result<Vec<AuditRecord>> - 古い順に並んだ監査ログのエントリ
+     */
+    pub fn read_audit_log(ctx: Context<ReadAuditLog>, offset: u64, limit: u8) -> Result<Vec<AuditRecord>> {
+        let audit_log = ctx.accounts.audit_log.load()?;
+        
+        let start = audit_log.first_sequence().saturating_add(offset);
+        let end = start
+            .saturating_add(limit.min(MAX_AUDIT_LOG_READ) as u64)
+            .min(audit_log.head);
+        
+        Ok((start..end).map(|sequence| audit_log.record(sequence)).collect())
+    }
+
+    /**
+     * ミント登録命令（registerMint）
+     * 
+     * 金庫で追加のSPLトークンを保管するために、金庫とミントから派生するトークンアカウントを作成し、
+     * ミント登録簿に追加します。登録したミントの引き出し上限は、初期状態では制限なしです。
+     * 保管できるトークンを増やすだけの操作のため、多重署名が設定されていても即時に反映されます。
+     * 
+     * @param ctx - ミント登録に必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn register_mint(ctx: Context<RegisterMint>) -> Result<()> {
+        // Verify owner
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        vault.record_owner_activity(current_timestamp);
+        
+        check_mint_extensions(&ctx.accounts.mint)?;
+        
+        let mint = ctx.accounts.mint.key();
+        require!(vault.token_account_for(&mint).is_none(), VaultError::MintAlreadyRegistered);
+        require!(vault.mints.len() < MAX_MINTS, VaultError::TooManyMints);
+        
+        vault.mints.push(MintRecord {
+            mint,
+            token_account: ctx.accounts.token_account.key(),
+            max_withdrawal_limit: u64::MAX, // デフォルトでは制限なし
+        });
+        
+        emit!(MintRegistered {
+            vault: vault.key(),
+            actor: ctx.accounts.owner.key(),
+            mint,
+            token_account: ctx.accounts.token_account.key(),
+            timestamp: current_timestamp,
+        });
+        record_audit_entry(
+            vault,
+            &ctx.accounts.audit_log,
+            AUDIT_ACTION_REGISTER_MINT,
+            ctx.accounts.owner.key(),
+            mint,
+            0,
+        )?;
+        
+        Ok(())
+    }
+
+    /**
+     * ミント登録解除命令（unregisterMint）
+     * 
+     * 登録済みのミントをミント登録簿から削除し、そのトークンアカウントを閉鎖して
+     * レントを所有者に返却します。トークンアカウントの残高は0である必要があります。
+     * 
+     * @param ctx - ミント登録解除に必要なアカウント情報を含むコンテキスト
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn unregister_mint(ctx: Context<UnregisterMint>) -> Result<()> {
+        // Verify owner
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        vault.record_owner_activity(current_timestamp);
+        
+        // Tokens must be withdrawn before the account is closed
+        require!(ctx.accounts.token_account.amount == 0, VaultError::MintAccountNotEmpty);
+        
+        let mint = ctx.accounts.token_account.mint;
+        let index = vault.mints.iter()
+            .position(|record| record.mint == mint)
+            .ok_or(VaultError::MintNotRegistered)?;
+        vault.mints.remove(index);
+        
+        // Close the token account, returning its rent to the owner
+        let creator = vault.creator;
+        let vault_id_bytes = vault.vault_id.to_le_bytes();
+        let bump = vault.bump;
+        let seeds = &[
+            b"vault".as_ref(),
+            creator.as_ref(),
+            vault_id_bytes.as_ref(),
+            &[bump],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = token_interface::CloseAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token_interface::close_account(cpi_ctx)?;
+        
+        emit!(MintUnregistered {
+            vault: ctx.accounts.vault.key(),
+            actor: ctx.accounts.owner.key(),
+            mint,
+            timestamp: current_timestamp,
+        });
+        record_audit_entry(
+            &ctx.accounts.vault,
+            &ctx.accounts.audit_log,
+            AUDIT_ACTION_UNREGISTER_MINT,
+            ctx.accounts.owner.key(),
+            mint,
+            0,
+        )?;
+        
+        Ok(())
+    }
+
+    /**
+     * ミント別引き出し制限設定命令（setMintWithdrawalLimit）
+     * 
+     * 指定したミントの1回の取引で引き出せる最大金額を設定します。
+     * ミントごとに小数点以下の桁数が異なるため、上限はミントごとに設定します。
+     * 初期化時のトークンを指定した場合は、set_withdrawal_limit命令と同じ上限を変更します。
+     * 多重署名が設定されている場合は、保留中のトランザクションとして記録されます。
+     * 
+     * @param ctx - 引き出し制限設定に必要なアカウント情報を含むコンテキスト
+     * @param mint - ミントのアドレス
+     * @param limit - 最大引き出し可能額
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn set_mint_withdrawal_limit(ctx: Context<SetWithdrawalLimit>, mint: Pubkey, limit: u64) -> Result<()> {
+        // Verify owner
+        require!(ctx.accounts.vault.owner == ctx.accounts.owner.key(), VaultError::Unauthorized);
+        require!(ctx.accounts.vault.token_account_for(&mint).is_some(), VaultError::MintNotRegistered);
+        
+        // Set the withdrawal limit, or propose it if multisig is enabled
+        submit_config_change(
+            &mut ctx.accounts.vault,
+            &mut ctx.accounts.pending_transaction,
+            &ctx.accounts.audit_log,
+            ctx.bumps.pending_transaction,
+            ctx.accounts.owner.key(),
+            TransactionType::SetMintWithdrawalLimit { mint, limit },
+        )
+    }
+}
+
+/**
+ * 設定変更ヘルパー関数
+ * 
+ * 多重署名が設定されていない場合は設定変更を即時に反映します。
+ * 多重署名が設定されている場合は、設定変更を保留中のトランザクションとして記録し、
+ * 必要な署名数の承認が集まった後にexecute_transaction命令で反映されます。
+ * 
+ * @param vault - 金庫アカウント
+ * @param pending_transaction - 多重署名の場合に作成する保留中トランザクションのアカウント
+ * @param audit_log - 監査ログのアカウント（作成済みの場合）
+ * @param bump - 保留中トランザクションPDAのバンプシード
+ * @param proposer - 設定変更を提案したアドレス
+ * @param change - 設定変更の内容
+ * @return Result<()> - 操作の成功または失敗
+ */
+fn submit_config_change<'info>(
+    vault: &mut Account<'info, Vault>,
+    pending_transaction: &mut Option<Account<'info, PendingTransaction>>,
+    audit_log: &Option<AccountLoader<'info, AuditLog>>,
+    bump: u8,
+    proposer: Pubkey,
+    change: TransactionType,
+) -> Result<()> {
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    vault.record_owner_activity(current_timestamp);
+    
+    // Single-sig mode, apply immediately
+    if vault.multisig_threshold <= 1 {
+        require!(pending_transaction.is_none(), VaultError::InvalidPendingTransactionAccount);
+        vault.apply_config_change(&change, current_timestamp)?;
+        emit_config_change(vault, &change, proposer, current_timestamp);
+        return record_config_change(vault, audit_log, &change, proposer);
+    }
+    
+    // This is a multisig vault, so we need to create a pending transaction
+    let pending_tx = pending_transaction.as_mut()
+        .ok_or(VaultError::InvalidPendingTransactionAccount)?;
+    
+    // Payloads that could never be applied, or would not fit the account, are rejected up front
+    vault.validate_config_change(&change)?;
+    let tx_id = vault.open_transaction();
+    
+    pending_tx.set_inner(PendingTransaction {
+        vault: vault.key(),
+        id: tx_id,
+        proposer,
+        transaction_type: change,
+        amount: 0, // Not relevant for configuration changes
+        destination: Pubkey::default(), // Not relevant for configuration changes
+        new_owner: None,
+        signers: vec![proposer],
+        rejections: Vec::new(),
+        threshold: vault.multisig_threshold,
+        status: TransactionStatus::Pending,
+        created_at: current_timestamp,
+        expires_at: vault.transaction_expiry(current_timestamp),
+        config_version: vault.config_version,
+        bump,
+    });
+    emit_transaction_proposed(pending_tx);
+    
+    Ok(())
+}
+
+/**
+ * 監査ログ記録ヘルパー関数
+ * 
+ * 監査ログのリングバッファに操作を追記します。
+ * 監査ログを作成した金庫では、監査ログのアカウントが渡されない操作は失敗します。
+ * 
+ * @param vault - 操作対象の金庫
+ * @param audit_log - 監査ログのアカウント（作成済みの場合）
+ * @param action - 操作の種類（AUDIT_ACTION_*）
+ * @param actor - 操作を行ったアドレス
+ * @param counterparty - 操作の相手（送金先、預け入れ元、新しい所有者など）
+ * @param amount - 操作した金額
+ * @return Result<()> - 操作の成功または失敗
+ */
+fn record_audit_entry(
+    vault: &Vault,
+    audit_log: &Option<AccountLoader<AuditLog>>,
+    action: u8,
+    actor: Pubkey,
+    counterparty: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let Some(audit_log) = audit_log else {
+        // Once created, the log cannot be skipped
+        require!(!vault.audit_log_enabled, VaultError::AuditLogRequired);
+        return Ok(());
+    };
+    
+    audit_log.load_mut()?.push(AuditEntry {
+        slot: Clock::get()?.slot,
+        amount,
+        actor,
+        counterparty,
+        action,
+        _padding: [0; 7],
+    });
+    
+    Ok(())
+}
+
+/**
+ * 設定変更の監査ログ記録ヘルパー関数
+ * 
+ * 反映された設定変更を、設定ごとの操作の種類と変更後の値で監査ログに記録します。
+ * 
+ * @param vault - 設定変更が反映された金庫
+ * @param audit_log - 監査ログのアカウント（作成済みの場合）
+ * @param change - 設定変更の内容
+ * @param actor - 設定変更を提案したアドレス
+ * @return Result<()> - 操作の成功または失敗
+ */
+fn record_config_change(
+    vault: &Vault,
+    audit_log: &Option<AccountLoader<AuditLog>>,
+    change: &TransactionType,
+    actor: Pubkey,
+) -> Result<()> {
+    let (action, counterparty, amount) = match change {
+        TransactionType::SetTimelock { .. } | TransactionType::SetTimelockUntil { .. } => {
+            (AUDIT_ACTION_SET_TIMELOCK, Pubkey::default(), vault.lock_until)
+        },
+        TransactionType::SetLockMode { mode } => {
+            (AUDIT_ACTION_SET_LOCK_MODE, Pubkey::default(), (*mode == LockMode::ExtendOnly) as u64)
+        },
+        TransactionType::AddDelegate { record } => {
+            (AUDIT_ACTION_ADD_DELEGATE, record.delegate, record.allowance)
+        },
+        TransactionType::SetMultisig { threshold, .. } => {
+            (AUDIT_ACTION_SET_MULTISIG, Pubkey::default(), *threshold as u64)
+        },
+        TransactionType::SetApprovalTiers { tiers } => {
+            (AUDIT_ACTION_SET_APPROVAL_TIERS, Pubkey::default(), tiers.len() as u64)
+        },
+        TransactionType::SetWithdrawalLimit { limit } => {
+            (AUDIT_ACTION_SET_WITHDRAWAL_LIMIT, vault.mint, *limit)
+        },
+        TransactionType::SetMintWithdrawalLimit { mint, limit } => {
+            (AUDIT_ACTION_SET_WITHDRAWAL_LIMIT, *mint, *limit)
+        },
+        TransactionType::SetWithdrawalWindow { window_limit, .. } => {
+            (AUDIT_ACTION_SET_WITHDRAWAL_WINDOW, Pubkey::default(), *window_limit)
+        },
+        TransactionType::SetWithdrawalDelay { delay } => {
+            (AUDIT_ACTION_SET_WITHDRAWAL_DELAY, Pubkey::default(), *delay)
+        },
+        TransactionType::SetAllowlist { enabled, delay } => {
+            let action = if *enabled { AUDIT_ACTION_ENABLE_ALLOWLIST } else { AUDIT_ACTION_DISABLE_ALLOWLIST };
+            (action, Pubkey::default(), *delay)
+        },
+        TransactionType::AddAllowlistEntry { address } => {
+            (AUDIT_ACTION_ADD_ALLOWLIST_ENTRY, *address, 0)
+        },
+        TransactionType::SetGuardians { guardians } => {
+            (AUDIT_ACTION_SET_GUARDIANS, Pubkey::default(), guardians.len() as u64)
+        },
+        TransactionType::Unpause => (AUDIT_ACTION_UNPAUSE, Pubkey::default(), 0),
+        TransactionType::SetRecovery { threshold, .. } => {
+            (AUDIT_ACTION_SET_RECOVERY, Pubkey::default(), *threshold as u64)
+        },
+        TransactionType::SetInheritance { beneficiary, inactivity_period } => {
+            (AUDIT_ACTION_SET_INHERITANCE, beneficiary.unwrap_or_default(), *inactivity_period)
+        },
+        TransactionType::SetTransactionTtl { ttl } => {
+            (AUDIT_ACTION_SET_TRANSACTION_TTL, Pubkey::default(), *ttl)
+        },
+        TransactionType::Withdraw | TransactionType::TransferOwnership | TransactionType::CloseVault => {
+            return Err(VaultError::InvalidTransactionType.into());
+        },
+    };
+    
+    record_audit_entry(vault, audit_log, action, actor, counterparty, amount)
+}
+
+/**
+ * 提案イベント発行ヘルパー関数
+ * 
+ * 新しく作成された保留中トランザクションの内容をTransactionProposedイベントとして発行します。
+ * 
+ * @param pending_tx - 作成された保留中トランザクション
+ */
+fn emit_transaction_proposed(pending_tx: &PendingTransaction) {
+    emit!(TransactionProposed {
+        vault: pending_tx.vault,
+        tx_id: pending_tx.id,
+        proposer: pending_tx.proposer,
+        transaction_type: pending_tx.transaction_type.clone(),
+        amount: pending_tx.amount,
+        threshold: pending_tx.threshold,
+        expires_at: pending_tx.expires_at,
+        timestamp: pending_tx.created_at,
+    });
+}
+
+/**
+ * 設定変更イベント発行ヘルパー関数
+ * 
+ * 反映された設定変更を、変更後の金庫の状態と共にイベントとして発行します。
+ * 個別のイベントがない設定変更はConfigChangedイベントとして発行されます。
+ * 
+ * @param vault - 設定変更が反映された金庫アカウント
+ * @param change - 設定変更の内容
+ * @param actor - 設定変更を提案したアドレス
+ * @param now - 現在のUNIXタイムスタンプ
+ */
+fn emit_config_change(vault: &Account<Vault>, change: &TransactionType, actor: Pubkey, now: u64) {
+    match change {
+        TransactionType::SetTimelock { .. }
+        | TransactionType::SetTimelockUntil { .. }
+        | TransactionType::SetLockMode { .. } => {
+            emit!(TimelockUpdated {
+                vault: vault.key(),
+                actor,
+                lock_until: vault.lock_until,
+                lock_mode: vault.lock_mode,
+                timestamp: now,
+            });
+        },
+        TransactionType::AddDelegate { record } => {
+            emit!(DelegateAdded {
+                vault: vault.key(),
+                actor,
+                delegate: record.delegate,
+                allowance: record.allowance,
+                refill_period: record.refill_period,
+                expires_at: record.expires_at,
+                permissions: record.permissions,
+                timestamp: now,
+            });
+        },
+        TransactionType::SetMultisig { .. } | TransactionType::SetApprovalTiers { .. } => {
+            emit!(MultisigUpdated {
+                vault: vault.key(),
+                actor,
+                threshold: vault.multisig_threshold,
+                signers: vault.multisig_signers.clone(),
+                approval_tiers: vault.approval_tiers.clone(),
+                timestamp: now,
+            });
+        },
+        TransactionType::SetWithdrawalLimit { .. } | TransactionType::SetWithdrawalWindow { .. } => {
+            emit!(WithdrawalLimitUpdated {
+                vault: vault.key(),
+                actor,
+                max_withdrawal_limit: vault.max_withdrawal_limit,
+                window_length: vault.window_length,
+                window_limit: vault.window_limit,
+                timestamp: now,
+            });
+        },
+        _ => {
+            emit!(ConfigChanged {
+                vault: vault.key(),
+                actor,
+                change: change.clone(),
+                timestamp: now,
+            });
+        },
+    }
+}
+
+/**
+ * ミント拡張機能確認ヘルパー関数
+ * 
+ * Token-2022のミントが、金庫で安全に扱える拡張機能のみを使用していることを確認します。
+ * 転送フック、永久委任者、譲渡不可、凍結状態の既定値、秘匿転送など、
+ * 金庫の外から資金を動かしたり転送を妨げたりできる拡張機能を持つミントは拒否します。
+ * 従来のSPLトークンのミントは拡張機能を持たないため、常に許可されます。
+ * 
+ * @param mint - 確認するミントのアカウント
+ * @return Result<()> - 操作の成功または失敗
+ */
+fn check_mint_extensions(mint: &InterfaceAccount<Mint>) -> Result<()> {
+    let mint_info = mint.to_account_info();
+    let data = mint_info.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    
+    for extension in state.get_extension_types()? {
+        match extension {
+            // Transfer fees are accounted for when depositing
+            ExtensionType::TransferFeeConfig
+            | ExtensionType::MintCloseAuthority
+            | ExtensionType::InterestBearingConfig
+            | ExtensionType::MetadataPointer
+            | ExtensionType::TokenMetadata => {},
+            _ => return Err(VaultError::UnsupportedMintExtension.into()),
+        }
+    }
+    
+    Ok(())
+}
+
+/**
+ * 引き出し実行ヘルパー関数
+ * 
+ * 金庫のトークンアカウントからユーザーのトークンアカウントにトークンを移動する
+ * 実際の転送処理を行います。金庫のPDA（Program Derived Address）を使用して
+ * 署名し、トークン転送のCPI（Cross-Program Invocation）を実行します。
+ * 
+ * @param vault - 金庫アカウント（署名用のシードを含む）
+ * @param vault_token_account - 金庫のトークンアカウント情報
+ * @param mint - 転送するトークンのミント（小数点以下の桁数の確認に使用）
+ * @param destination_token_account - 宛先のトークンアカウント情報
+ * @param token_program - SPLトークンまたはToken-2022プログラム情報
+ * @param amount - 引き出すトークンの量
+ * @return Result<()> - 操作の成功または失敗
+ */
+fn execute_withdraw<'info>(
+    vault: &Account<'info, Vault>,
+    vault_token_account: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    destination_token_account: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    // Create signer seeds for PDA
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[
+        b"vault".as_ref(),
+        vault.creator.as_ref(),
+        vault_id_bytes.as_ref(),
+        &[vault.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    // Execute the transfer
+    let cpi_accounts = TransferChecked {
+        from: vault_token_account,
+        mint: mint.to_account_info(),
+        to: destination_token_account,
+        authority: vault.to_account_info(),
+    };
+    
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)?;
+    
+    Ok(())
+}
+
+/**
+ * 金庫初期化用のアカウント構造体
+ * 
+ * 金庫の初期化時に必要な全てのアカウント情報を定義します。
+ * この構造体はinitialize命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+#[instruction(vault_id: u64)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = Vault::space(DEFAULT_MAX_DELEGATES, DEFAULT_MAX_SIGNERS, DEFAULT_MAX_ALLOWLIST),
+        seeds = [b"vault", owner.key().as_ref(), vault_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        init,
+        payer = owner,
+        token::mint = mint,
+        token::authority = vault,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/**
+ * トークン預け入れ用のアカウント構造体
+ * 
+ * トークンを金庫に預け入れる際に必要な全てのアカウント情報を定義します。
+ * この構造体はdeposit命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+#[instruction(amount: u64, mint: Pubkey)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        mut,
+        constraint = vault.token_account_for(&mint) == Some(vault_token_account.key()) @ VaultError::MintNotRegistered,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = mint @ VaultError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.owner == owner.key(),
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/**
+ * トークン引き出し用のアカウント構造体
+ * 
+ * トークンを金庫から引き出す際に必要な全てのアカウント情報を定義します。
+ * この構造体はwithdraw命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+#[instruction(amount: u64, mint: Pubkey)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        mut,
+        constraint = vault.token_account_for(&mint) == Some(vault_token_account.key()) @ VaultError::MintNotRegistered,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = mint @ VaultError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.owner == owner.key(),
+        constraint = user_token_account.mint == vault_token_account.mint @ VaultError::InvalidMint,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PendingTransaction::space(vault.max_signers),
+        seeds = [b"transaction", vault.key().as_ref(), vault.transaction_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pending_transaction: Option<Account<'info, PendingTransaction>>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * 残高確認用のアカウント構造体
+ * 
+ * 金庫内のトークン残高を確認する際に必要なアカウント情報を定義します。
+ * この構造体はquery_balance命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct QueryBalance<'info> {
+    #[account(
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        constraint = vault.token_account_for(&token_account.mint) == Some(token_account.key()),
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+}
+
+/**
+ * タイムロック設定用のアカウント構造体
+ * 
+ * 金庫にタイムロックを設定する際に必要なアカウント情報を定義します。
+ * この構造体はset_timelock命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct SetTimelock<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PendingTransaction::space(vault.max_signers),
+        seeds = [b"transaction", vault.key().as_ref(), vault.transaction_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pending_transaction: Option<Account<'info, PendingTransaction>>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * 委任者管理用のアカウント構造体
+ * 
+ * 委任者の追加・削除に必要なアカウント情報を定義します。
+ * この構造体はadd_delegate命令とremove_delegate命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct ManageDelegate<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PendingTransaction::space(vault.max_signers),
+        seeds = [b"transaction", vault.key().as_ref(), vault.transaction_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pending_transaction: Option<Account<'info, PendingTransaction>>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * 許可リスト管理用のアカウント構造体
+ * 
+ * 送金先の許可リストの設定・追加・削除に必要なアカウント情報を定義します。
+ * この構造体はset_allowlist命令、add_allowlist_entry命令、remove_allowlist_entry命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct ManageAllowlist<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PendingTransaction::space(vault.max_signers),
+        seeds = [b"transaction", vault.key().as_ref(), vault.transaction_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pending_transaction: Option<Account<'info, PendingTransaction>>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * 多重署名設定用のアカウント構造体
+ * 
+ * 多重署名（マルチシグ）設定に必要なアカウント情報を定義します。
+ * この構造体はset_multisig命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct SetMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PendingTransaction::space(vault.max_signers),
+        seeds = [b"transaction", vault.key().as_ref(), vault.transaction_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pending_transaction: Option<Account<'info, PendingTransaction>>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetWithdrawalLimit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PendingTransaction::space(vault.max_signers),
+        seeds = [b"transaction", vault.key().as_ref(), vault.transaction_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pending_transaction: Option<Account<'info, PendingTransaction>>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * 所有権譲渡開始用のアカウント構造体
+ * 
+ * 所有権譲渡を開始する際に必要なアカウント情報を定義します。
+ * この構造体はinitiate_ownership_transfer命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct InitiateOwnershipTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PendingTransaction::space(vault.max_signers),
+        seeds = [b"transaction", vault.key().as_ref(), vault.transaction_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pending_transaction: Option<Account<'info, PendingTransaction>>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * 所有権譲渡承認用のアカウント構造体
+ * 
+ * 所有権譲渡を承認する際に必要なアカウント情報を定義します。
+ * この構造体はaccept_ownership命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct AcceptOwnership<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+}
+
+/**
+ * 所有権譲渡キャンセル用のアカウント構造体
+ * 
+ * 所有権譲渡をキャンセルする際に必要なアカウント情報を定義します。
+ * この構造体はcancel_ownership_transfer命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct CancelOwnershipTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        mut,
+        constraint = pending_transaction.vault == vault.key(),
+    )]
+    pub pending_transaction: Option<Account<'info, PendingTransaction>>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/**
+ * トランザクション承認用のアカウント構造体
+ * 
+ * 保留中のトランザクションを承認する際に必要なアカウント情報を定義します。
+ * この構造体はapprove_transaction命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+#[instruction(tx_id: u64)]
+pub struct ApproveTransaction<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"transaction", vault.key().as_ref(), tx_id.to_le_bytes().as_ref()],
+        bump = pending_transaction.bump,
+    )]
+    pub pending_transaction: Account<'info, PendingTransaction>,
+    
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/**
+ * トランザクション実行用のアカウント構造体
+ * 
+ * 承認済みの保留中トランザクションを実行する際に必要なアカウント情報を定義します。
+ * この構造体はexecute_transaction命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+#[instruction(tx_id: u64)]
+pub struct ExecuteTransaction<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        mut,
+        seeds = [b"transaction", vault.key().as_ref(), tx_id.to_le_bytes().as_ref()],
+        bump = pending_transaction.bump,
+    )]
+    pub pending_transaction: Account<'info, PendingTransaction>,
+    
+    #[account(
+        mut,
+        constraint = vault.token_account_for(&vault_token_account.mint) == Some(vault_token_account.key()),
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(constraint = token_mint.key() == vault_token_account.mint @ VaultError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    // 引き出しの場合のみ必要です
+    #[account(mut)]
+    pub destination_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/**
+ * 金庫閉鎖用のアカウント構造体
+ * 
+ * 金庫を閉鎖する際に必要なアカウント情報を定義します。
+ * この構造体はclose_vault命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct CloseVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です（金庫と共に閉鎖されます）
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.token_account,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(constraint = token_mint.key() == vault_token_account.mint @ VaultError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == vault_token_account.mint @ VaultError::InvalidMint,
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PendingTransaction::space(vault.max_signers),
+        seeds = [b"transaction", vault.key().as_ref(), vault.transaction_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pending_transaction: Option<Account<'info, PendingTransaction>>,
+    
+    #[account(
+        mut,
+        constraint = approved_transaction.vault == vault.key(),
+    )]
+    pub approved_transaction: Option<Account<'info, PendingTransaction>>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * 金庫サイズ変更用のアカウント構造体
+ * 
+ * 金庫アカウントを再割り当てする際に必要なアカウント情報を定義します。
+ * この構造体はresize_vault命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+#[instruction(max_delegates: u16, max_signers: u16, max_allowlist: u16)]
+pub struct ResizeVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+        realloc = Vault::space(max_delegates, max_signers, max_allowlist),
+        realloc::payer = owner,
+        realloc::zero = false,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * 保留中トランザクション閉鎖用のアカウント構造体
+ * 
+ * 処理済みの保留中トランザクションを閉鎖する際に必要なアカウント情報を定義します。
+ * 金庫が閉鎖された後でもレントを回収できるよう、金庫アカウントは必要としません。
+ * この構造体はclose_transaction命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct CloseTransaction<'info> {
+    #[account(
+        mut,
+        seeds = [b"transaction", pending_transaction.vault.as_ref(), pending_transaction.id.to_le_bytes().as_ref()],
+        bump = pending_transaction.bump,
+        has_one = proposer,
+        close = proposer,
+    )]
+    pub pending_transaction: Account<'info, PendingTransaction>,
+    
+    #[account(mut)]
+    pub proposer: SystemAccount<'info>,
+}
+
+/**
+ * 保留中トランザクション有効期間設定用のアカウント構造体
+ * 
+ * 保留中トランザクションの有効期間を設定する際に必要なアカウント情報を定義します。
+ * 多重署名が設定されている場合は、保留中トランザクションのアカウントを作成します。
+ * この構造体はset_transaction_ttl命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct SetTransactionTtl<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PendingTransaction::space(vault.max_signers),
+        seeds = [b"transaction", vault.key().as_ref(), vault.transaction_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pending_transaction: Option<Account<'info, PendingTransaction>>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * 期限切れトランザクション整理用のアカウント構造体
+ * 
+ * 期限切れの保留中トランザクションを無効にする際に必要なアカウント情報を定義します。
+ * この構造体はexpire_transaction命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct ExpireTransaction<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"transaction", vault.key().as_ref(), pending_transaction.id.to_le_bytes().as_ref()],
+        bump = pending_transaction.bump,
+        has_one = proposer,
+        close = proposer,
+    )]
+    pub pending_transaction: Account<'info, PendingTransaction>,
+    
+    #[account(mut)]
+    pub proposer: SystemAccount<'info>,
+}
+
+/**
+ * 保留中トランザクション管理用のアカウント構造体
+ * 
+ * 保留中のトランザクションへの投票やキャンセルに必要なアカウント情報を定義します。
+ * この構造体はrevoke_approval命令、reject_transaction命令、cancel_transaction命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+#[instruction(tx_id: u64)]
+pub struct ManageTransaction<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"transaction", vault.key().as_ref(), tx_id.to_le_bytes().as_ref()],
+        bump = pending_transaction.bump,
+    )]
+    pub pending_transaction: Account<'info, PendingTransaction>,
+    
+    pub signer: Signer<'info>,
+}
+
+/**
+ * 引き出し予約用のアカウント構造体
+ * 
+ * 引き出し予約を作成する際に必要なアカウント情報を定義します。
+ * この構造体はrequest_withdrawal命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+#[instruction(amount: u64, mint: Pubkey)]
+pub struct RequestWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        init,
+        payer = requester,
+        space = WithdrawalRequest::SPACE,
+        seeds = [b"withdrawal", vault.key().as_ref(), vault.withdrawal_request_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+    
+    #[account(
+        constraint = destination_token_account.mint == vault_token_account.mint @ VaultError::InvalidMint,
+        constraint = destination_token_account.owner == requester.key(),
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = vault.token_account_for(&mint) == Some(vault_token_account.key()) @ VaultError::MintNotRegistered,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub requester: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * 引き出し予約実行用のアカウント構造体
+ * 
+ * 待機時間が経過した引き出し予約を実行する際に必要なアカウント情報を定義します。
+ * この構造体はexecute_withdrawal命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct ExecuteWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        mut,
+        seeds = [b"withdrawal", vault.key().as_ref(), withdrawal_request.id.to_le_bytes().as_ref()],
+        bump = withdrawal_request.bump,
+        has_one = vault,
+        has_one = requester,
+        close = requester,
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+    
+    #[account(
+        mut,
+        constraint = vault.token_account_for(&mint) == Some(vault_token_account.key()) @ VaultError::MintNotRegistered,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = mint @ VaultError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = destination_token_account.key() == withdrawal_request.destination @ VaultError::DestinationMismatch,
+        constraint = destination_token_account.mint == vault_token_account.mint @ VaultError::InvalidMint,
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub requester: SystemAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/**
+ * 引き出し予約キャンセル用のアカウント構造体
+ * 
+ * 待機時間中の引き出し予約を取り消す際に必要なアカウント情報を定義します。
+ * この構造体はcancel_withdrawal命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"withdrawal", vault.key().as_ref(), withdrawal_request.id.to_le_bytes().as_ref()],
+        bump = withdrawal_request.bump,
+        has_one = vault,
+        has_one = requester,
+        close = requester,
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+    
+    #[account(mut)]
+    pub requester: SystemAccount<'info>,
+    
+    pub signer: Signer<'info>,
+}
+
+/**
+ * 権利確定スケジュール作成用のアカウント構造体
+ * 
+ * 権利確定スケジュールを作成する際に必要なアカウント情報を定義します。
+ * この構造体はcreate_vesting_schedule命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey)]
+pub struct CreateVestingSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = VestingSchedule::SPACE,
+        seeds = [b"vesting", vault.key().as_ref(), vault.vesting_schedule_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        constraint = vault_token_account.key() == vault.token_account,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    // 許可リストの確認に使用する受益者のトークンアカウント
+    #[account(
+        constraint = beneficiary_token_account.owner == beneficiary @ VaultError::DestinationMismatch,
+        constraint = beneficiary_token_account.mint == vault_token_account.mint @ VaultError::InvalidMint,
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * 権利確定分支払い用のアカウント構造体
+ * 
+ * 権利確定した金額を受益者に支払う際に必要なアカウント情報を定義します。
+ * この構造体はrelease_vested命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct ReleaseVested<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        mut,
+        seeds = [b"vesting", vault.key().as_ref(), vesting_schedule.id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump,
+        has_one = vault,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.token_account,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(constraint = token_mint.key() == vault_token_account.mint @ VaultError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = beneficiary_token_account.owner == vesting_schedule.beneficiary @ VaultError::DestinationMismatch,
+        constraint = beneficiary_token_account.mint == vault_token_account.mint @ VaultError::InvalidMint,
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/**
+ * 権利確定スケジュール取り消し用のアカウント構造体
+ * 
+ * 権利確定スケジュールの未確定分を取り消す際に必要なアカウント情報を定義します。
+ * この構造体はrevoke_vesting命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct RevokeVesting<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"vesting", vault.key().as_ref(), vesting_schedule.id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump,
+        has_one = vault,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    pub owner: Signer<'info>,
+}
+
+/**
+ * 権利確定スケジュール閉鎖用のアカウント構造体
+ * 
+ * 支払いが完了した権利確定スケジュールを閉鎖する際に必要なアカウント情報を定義します。
+ * 金庫が閉鎖された後でもレントを回収できるよう、金庫アカウントは必要としません。
+ * この構造体はclose_vesting_schedule命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct CloseVestingSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [b"vesting", vesting_schedule.vault.as_ref(), vesting_schedule.id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump,
+        has_one = funder,
+        close = funder,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(mut)]
+    pub funder: SystemAccount<'info>,
+}
+
+/**
+ * 定期支払い作成用のアカウント構造体
+ * 
+ * 定期支払いを作成する際に必要なアカウント情報を定義します。
+ * この構造体はcreate_payment_stream命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct CreatePaymentStream<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PaymentStream::SPACE,
+        seeds = [b"stream", vault.key().as_ref(), vault.payment_stream_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub payment_stream: Account<'info, PaymentStream>,
+    
+    #[account(
+        constraint = recipient_token_account.mint == vault_token_account.mint @ VaultError::InvalidMint,
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = vault_token_account.key() == vault.token_account,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * 定期支払い実行用のアカウント構造体
+ * 
+ * 経過した期間分の支払いを実行する際に必要なアカウント情報を定義します。
+ * この構造体はclaim_stream命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct ClaimStream<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        mut,
+        seeds = [b"stream", vault.key().as_ref(), payment_stream.id.to_le_bytes().as_ref()],
+        bump = payment_stream.bump,
+        has_one = vault,
+    )]
+    pub payment_stream: Account<'info, PaymentStream>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.token_account,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(constraint = token_mint.key() == vault_token_account.mint @ VaultError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = recipient_token_account.key() == payment_stream.recipient @ VaultError::DestinationMismatch,
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/**
+ * 定期支払い停止用のアカウント構造体
+ * 
+ * 定期支払いを停止する際に必要なアカウント情報を定義します。
+ * この構造体はcancel_payment_stream命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct CancelPaymentStream<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"stream", vault.key().as_ref(), payment_stream.id.to_le_bytes().as_ref()],
+        bump = payment_stream.bump,
+        has_one = vault,
+        has_one = funder,
+        close = funder,
+    )]
+    pub payment_stream: Account<'info, PaymentStream>,
+    
+    #[account(mut)]
+    pub funder: SystemAccount<'info>,
+    
+    pub owner: Signer<'info>,
+}
+
+/**
+ * ガーディアン管理用のアカウント構造体
+ * 
+ * ガーディアンの設定と一時停止の解除に必要なアカウント情報を定義します。
+ * この構造体はset_guardians命令とunpause命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct ManageGuardians<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PendingTransaction::space(vault.max_signers),
+        seeds = [b"transaction", vault.key().as_ref(), vault.transaction_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub pending_transaction: Option<Account<'info, PendingTransaction>>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * リカバリー用のアカウント構造体
+ * 
+ * ガーディアンによるリカバリーの提案・承認・拒否・確定に必要なアカウント情報を定義します。
+ * この構造体はpropose_recovery命令、approve_recovery命令、veto_recovery命令、
+ * finalize_recovery命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct Recovery<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    pub signer: Signer<'info>,
+}
+
+/**
+ * 生存確認用のアカウント構造体
+ * 
+ * 所有者の活動を記録する際に必要なアカウント情報を定義します。
+ * この構造体はheartbeat命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct Heartbeat<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub owner: Signer<'info>,
+}
+
+/**
+ * 相続請求用のアカウント構造体
+ * 
+ * 受益者が金庫を請求する際に必要なアカウント情報を定義します。
+ * この構造体はclaim_inheritance命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct ClaimInheritance<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.token_account,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(constraint = token_mint.key() == vault_token_account.mint @ VaultError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    // 残高を受け取る場合のみ必要です
+    #[account(mut)]
+    pub beneficiary_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub beneficiary: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/**
+ * 監査ログ作成用のアカウント構造体
+ * 
+ * 金庫の監査ログを作成する際に必要なアカウント情報を定義します。
+ * この構造体はinit_audit_log命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct InitAuditLog<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = AuditLog::SPACE,
+        seeds = [b"audit_log", vault.key().as_ref()],
+        bump,
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * 監査ログ読み取り用のアカウント構造体
+ * 
+ * 監査ログを読み取る際に必要なアカウント情報を定義します。
+ * この構造体はread_audit_log命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct ReadAuditLog<'info> {
+    #[account(
+        seeds = [b"audit_log", audit_log.load()?.vault.as_ref()],
+        bump,
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+/**
+ * ミント登録用のアカウント構造体
+ * 
+ * 金庫に追加のミントを登録する際に必要なアカウント情報を定義します。
+ * この構造体はregister_mint命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct RegisterMint<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = owner,
+        token::mint = mint,
+        token::authority = vault,
+        seeds = [b"token", vault.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/**
+ * ミント登録解除用のアカウント構造体
+ * 
+ * 登録済みのミントを削除する際に必要なアカウント情報を定義します。
+ * この構造体はunregister_mint命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct UnregisterMint<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    #[account(
+        mut,
+        seeds = [b"token", vault.key().as_ref(), token_account.mint.as_ref()],
+        bump,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/**
+ * 一時停止用のアカウント構造体
+ * 
+ * 金庫を一時停止する際に必要なアカウント情報を定義します。
+ * この構造体はpause命令の実行時に使用されます。
+ */
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    // 監査ログを作成した金庫でのみ必要です
+    #[account(mut, has_one = vault)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    
+    pub signer: Signer<'info>,
+}
+
+/**
+ * 金庫アカウントのデータ構造体
+ * 
+ * 金庫の全ての状態を保存するためのメインデータ構造体です。
+ * 所有者情報、トークンアカウント、セキュリティ設定、トランザクション情報などを含みます。
+ */
+#[account]
+pub struct Vault {
+    pub owner: Pubkey,                         // 金庫の所有者
+    pub creator: Pubkey,                       // 金庫の作成者（PDAのシードに使用、変更不可）
+    pub vault_id: u64,                         // 作成者ごとの金庫ID（PDAのシードに使用）
+    pub token_account: Pubkey,                 // 金庫のトークンアカウント
+    pub bump: u8,                              // PDAのバンプシード
+    pub lock_until: u64,                       // タイムロック期限のUNIXタイムスタンプ
+    pub delegates: Vec<DelegateRecord>,        // 委任者ごとの権限と引き出し枠のリスト
+    pub multisig_threshold: u8,                // 必要な署名者数
+    pub multisig_signers: Vec<Pubkey>,         // 追加の署名者リスト（所有者は含まない）
+    pub transaction_count: u64,                // 次の保留中トランザクションのID（連番）
+    pub open_transactions: u32,                // 承認待ちの保留中トランザクション数
+    pub max_withdrawal_limit: u64,             // 最大引き出し可能金額
+    pub transfer_ownership_to: Option<Pubkey>, // 所有権譲渡先
+    pub max_delegates: u16,                    // 委任者の最大数
+    pub max_signers: u16,                      // 追加署名者の最大数
+    pub transaction_ttl: u64,                  // 保留中トランザクションの有効期間（秒、0は期限なし）
+    pub config_version: u32,                   // 署名者設定のバージョン（署名者・承認段階・所有者の変更のたびに更新）
+    pub window_length: u64,                    // 引き出し枠の期間（秒、0は制限なし）
+    pub window_limit: u64,                     // 期間内の最大引き出し可能金額
+    pub window_bucket: u64,                    // 最後に引き出しを記録した区間の番号
+    pub window_spent: [u64; WINDOW_BUCKETS],   // 区間ごとの引き出し済み金額（区間の番号で循環）
+    pub allowlist_enabled: bool,               // 送金先の許可リストが有効かどうか
+    pub allowlist_delay: u64,                  // 追加した送金先が有効になるまでの待機時間（秒）
+    pub pending_allowlist: Option<PendingAllowlistChange>, // 待機時間の経過後に反映される許可リストの緩和
+    pub allowlist: Vec<AllowlistEntry>,        // 送金先の許可リスト
+    pub max_allowlist: u16,                    // 許可リストの最大登録数
+    pub withdrawal_delay: u64,                 // 引き出し予約から実行までの待機時間（秒、0は待機なし）
+    pub withdrawal_request_count: u64,         // 次の引き出し予約のID（連番）
+    pub open_withdrawal_requests: u32,         // 実行またはキャンセルされていない引き出し予約の数
+    pub approval_tiers: Vec<ApprovalTier>,     // 引き出し金額別の承認段階（上限金額の昇順）
+    pub lock_mode: LockMode,                   // タイムロックの変更方法
+    pub reserved_amount: u64,                  // 権利確定スケジュールのために確保された未払いの金額
+    pub vesting_schedule_count: u64,           // 次の権利確定スケジュールのID（連番）
+    pub payment_stream_count: u64,             // 次の定期支払いのID（連番）
+    pub open_payment_streams: u32,             // 停止されていない定期支払いの数
+    pub guardians: Vec<Pubkey>,                // 一時停止の権限を持つガーディアンのリスト
+    pub paused: bool,                          // 一時停止中かどうか
+    pub recovery_threshold: u8,                // リカバリーに必要なガーディアンの承認数（0は無効）
+    pub recovery_delay: u64,                   // 承認が集まってからリカバリーを確定できるまでの待機時間（秒）
+    pub pending_recovery: Option<PendingRecovery>, // 進行中のリカバリー
+    pub last_owner_activity: u64,              // 所有者が最後に署名した操作のUNIXタイムスタンプ
+    pub inactivity_period: u64,                // 相続を請求できるまでの所有者の無活動期間（秒、0は無効）
+    pub beneficiary: Option<Pubkey>,           // 相続の受益者
+    pub audit_log_enabled: bool,               // 監査ログが作成済みかどうか
+    pub mint: Pubkey,                          // 初期化時のトークン（token_account）のミント
+    pub mints: Vec<MintRecord>,                // 追加で登録したミントの登録簿
+}
+
+impl Vault {
+    /**
+     * 指定した容量で金庫アカウントに必要なバイト数を計算します。
+     * 
+     * @param max_delegates - 委任者の最大数
+     * @param max_signers - 追加署名者の最大数
+     * @param max_allowlist - 許可リストの最大登録数
+     * @return usize - アカウントディスクリミネータを含むバイト数
+     */
+    pub fn space(max_delegates: u16, max_signers: u16, max_allowlist: u16) -> usize {
+        8 +                                     // ディスクリミネータ
+        32 + 32 + 8 + 32 + 1 + 8 +              // owner, creator, vault_id, token_account, bump, lock_until
+        4 + (max_delegates as usize * DelegateRecord::SPACE) + // delegates
+        1 +                                     // multisig_threshold
+        4 + (max_signers as usize * 32) +       // multisig_signers
+        8 + 4 +                                 // transaction_count, open_transactions
+        8 + 33 +                                // max_withdrawal_limit, transfer_ownership_to
+        2 + 2 +                                 // max_delegates, max_signers
+        8 + 4 +                                 // transaction_ttl, config_version
+        8 + 8 + 8 +                             // window_length, window_limit, window_bucket
+        (WINDOW_BUCKETS * 8) +                  // window_spent
+        1 + 8 +                                 // allowlist_enabled, allowlist_delay
+        1 + PendingAllowlistChange::SPACE +     // pending_allowlist
+        4 + (max_allowlist as usize * AllowlistEntry::SPACE) + // allowlist
+        2 +                                     // max_allowlist
+        8 + 8 + 4 +                             // withdrawal_delay, withdrawal_request_count, open_withdrawal_requests
+        4 + (MAX_APPROVAL_TIERS * ApprovalTier::SPACE) + // approval_tiers
+        1 +                                     // lock_mode
+        8 + 8 +                                 // reserved_amount, vesting_schedule_count
+        8 + 4 +                                 // payment_stream_count, open_payment_streams
+        4 + (MAX_GUARDIANS * 32) +              // guardians
+        1 +                                     // paused
+        1 + 8 +                                 // recovery_threshold, recovery_delay
+        1 + PendingRecovery::SPACE +            // pending_recovery
+        8 + 8 + 33 +                            // last_owner_activity, inactivity_period, beneficiary
+        1 +                                     // audit_log_enabled
+        32 +                                    // mint
+        4 + (MAX_MINTS * MintRecord::SPACE)     // mints
+    }
+
+    /**
+     * 新しい保留中トランザクションのIDを払い出し、承認待ちの件数を増やします。
+     * 
+     * @return u64 - 払い出したトランザクションID
+     */
+    pub fn open_transaction(&mut self) -> u64 {
+        let tx_id = self.transaction_count;
+        self.transaction_count += 1;
+        self.open_transactions += 1;
+        tx_id
+    }
+
+    /**
+     * 保留中トランザクションが実行またはキャンセルされたときに、承認待ちの件数を減らします。
+     */
+    pub fn settle_transaction(&mut self) {
+        self.open_transactions = self.open_transactions.saturating_sub(1);
+    }
+
+    /**
+     * 新しい引き出し予約のIDを払い出し、実行待ちの予約の件数を増やします。
+     * 
+     * @return u64 - 払い出した引き出し予約ID
+     */
+    pub fn open_withdrawal_request(&mut self) -> u64 {
+        let request_id = self.withdrawal_request_count;
+        self.withdrawal_request_count += 1;
+        self.open_withdrawal_requests += 1;
+        request_id
+    }
+
+    /**
+     * 引き出し予約が実行またはキャンセルされたときに、実行待ちの予約の件数を減らします。
+     */
+    pub fn settle_withdrawal_request(&mut self) {
+        self.open_withdrawal_requests = self.open_withdrawal_requests.saturating_sub(1);
+    }
+
+    /**
+     * 設定変更を金庫に反映します。
+     * 
+     * 単一署名の場合は各設定命令から、多重署名の場合はexecute_transaction命令から呼び出されます。
+     * 
+     * @param change - 設定変更の内容
+     * @param now - 現在のUNIXタイムスタンプ
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn apply_config_change(&mut self, change: &TransactionType, now: u64) -> Result<()> {
+        // Thresholds are checked again as the signer set may have changed since the proposal
+        self.validate_config_change(change)?;
+        
+        match change {
+            TransactionType::SetTimelock { lock_duration } => {
+                // Set the lock until timestamp (current time + duration)
+                let lock_until = now.checked_add(*lock_duration).ok_or(VaultError::TimelockOverflow)?;
+                self.set_lock_until(lock_until)?;
+            },
+            TransactionType::SetTimelockUntil { unlock_at } => {
+                self.set_lock_until(*unlock_at)?;
+            },
+            TransactionType::SetGuardians { guardians } => {
+                self.guardians = guardians.clone();
+                
+                // Approvals collected from the previous guardian set no longer count
+                self.pending_recovery = None;
+            },
+            TransactionType::SetInheritance { beneficiary, inactivity_period } => {
+                self.beneficiary = *beneficiary;
+                self.inactivity_period = *inactivity_period;
+            },
+            TransactionType::SetRecovery { threshold, delay } => {
+                self.recovery_threshold = *threshold;
+                self.recovery_delay = *delay;
+                self.pending_recovery = None;
+            },
+            TransactionType::Unpause => {
+                self.paused = false;
+            },
+            TransactionType::SetLockMode { mode } => {
+                // Extend-only locks cannot be relaxed again
+                require!(
+                    self.lock_mode != LockMode::ExtendOnly || *mode == LockMode::ExtendOnly,
+                    VaultError::LockModeIrreversible
+                );
+                self.lock_mode = *mode;
+            },
+            TransactionType::AddDelegate { record } => {
+                let mut record = record.clone();
+                record.period_start = now;
+                
+                // Check if already a delegate
+                if let Some(existing) = self.delegates.iter_mut().find(|d| d.delegate == record.delegate) {
+                    // Replace the existing terms
+                    *existing = record;
+                } else {
+                    require!(
+                        self.delegates.len() < self.max_delegates as usize,
+                        VaultError::TooManyDelegates
+                    );
+                    
+                    // Add the delegate
+                    self.delegates.push(record);
+                }
+            },
+            TransactionType::SetMultisig { threshold, signers } => {
+                // Set multisig configuration
+                self.multisig_threshold = *threshold;
+                self.multisig_signers = signers.clone();
+                
+                // Approvals collected under the previous signer set no longer count
+                self.config_version = self.config_version.wrapping_add(1);
+            },
+            TransactionType::SetWithdrawalLimit { limit } => {
+                // Set the withdrawal limit
+                self.max_withdrawal_limit = *limit;
+            },
+            TransactionType::SetMintWithdrawalLimit { mint, limit } => {
+                if *mint == self.mint {
+                    self.max_withdrawal_limit = *limit;
+                } else {
+                    let record = self.mints.iter_mut()
+                        .find(|record| record.mint == *mint)
+                        .ok_or(VaultError::MintNotRegistered)?;
+                    record.max_withdrawal_limit = *limit;
+                }
+            },
+            TransactionType::SetWithdrawalWindow { window_length, window_limit } => {
+                // Amounts already spent in the current window keep counting,
+                // carried into the current bucket of the new window
+                let spent = self.window_spent_total(now);
+                self.window_length = *window_length;
+                self.window_limit = *window_limit;
+                self.window_spent = [0; WINDOW_BUCKETS];
+                if *window_length > 0 {
+                    self.window_bucket = now / self.window_bucket_length();
+                    self.window_spent[(self.window_bucket % WINDOW_BUCKETS as u64) as usize] = spent;
+                }
+            },
+            TransactionType::AddAllowlistEntry { address } => {
+                // Existing entries keep their activation time
+                if !self.allowlist.iter().any(|entry| entry.address == *address) {
+                    require!(
+                        self.allowlist.len() < self.max_allowlist as usize,
+                        VaultError::TooManyAllowlistEntries
+                    );
+                    
+                    // New entries only become usable after the delay
+                    self.allowlist.push(AllowlistEntry {
+                        address: *address,
+                        active_at: now.saturating_add(self.allowlist_settings(now).1),
+                    });
+                }
+            },
+            TransactionType::SetAllowlist { enabled, delay } => {
+                let (current_enabled, current_delay) = self.allowlist_settings(now);
+                self.allowlist_enabled = current_enabled;
+                self.allowlist_delay = current_delay;
+                
+                // Disabling the list or shortening the delay must wait out the current delay,
+                // otherwise a stolen key could switch it off and withdraw right away
+                let loosens = current_enabled && (!*enabled || *delay < current_delay);
+                if loosens {
+                    self.pending_allowlist = Some(PendingAllowlistChange {
+                        enabled: *enabled,
+                        delay: *delay,
+                        active_at: now.saturating_add(current_delay),
+                    });
+                } else {
+                    // Tightening takes effect at once and discards any pending relaxation
+                    self.allowlist_enabled = *enabled;
+                    self.allowlist_delay = *delay;
+                    self.pending_allowlist = None;
+                }
+            },
+            TransactionType::SetWithdrawalDelay { delay } => {
+                // Requests that were already announced keep their original delay
+                self.withdrawal_delay = *delay;
+            },
+            TransactionType::SetApprovalTiers { tiers } => {
+                self.approval_tiers = tiers.clone();
+                
+                // Approvals collected under the previous tiers no longer count
+                self.config_version = self.config_version.wrapping_add(1);
+            },
+            TransactionType::SetTransactionTtl { ttl } => {
+                // Proposals that already exist keep their expiry
+                self.transaction_ttl = *ttl;
+            },
+            _ => return Err(VaultError::InvalidTransactionType.into()),
+        }
+        
+        Ok(())
+    }
+
+    /**
+     * 設定変更の内容が現在の金庫に対して有効かを検証します。
+     * 
+     * 保留中のトランザクションに記録できる大きさか、閾値が署名者やガーディアンの数を超えていないかを確認します。
+     * 提案時と反映時の両方で呼び出されます。
+     * 
+     * @param change - 設定変更の内容
+     * @return Result<()> - 有効な場合はOk、無効な場合はエラー
+     */
+    pub fn validate_config_change(&self, change: &TransactionType) -> Result<()> {
+        match change {
+            TransactionType::SetGuardians { guardians } => {
+                require!(guardians.len() <= MAX_GUARDIANS, VaultError::TooManyGuardians);
+                require!(
+                    self.recovery_threshold as usize <= guardians.len(),
+                    VaultError::InvalidThreshold
+                );
+            },
+            TransactionType::SetRecovery { threshold, .. } => {
+                require!(*threshold as usize <= self.guardians.len(), VaultError::InvalidThreshold);
+            },
+            TransactionType::SetMultisig { threshold, signers } => {
+                require!(*threshold > 0, VaultError::InvalidThreshold);
+                require!(
+                    *threshold as usize <= signers.len() + 1, // +1 for owner
+                    VaultError::InvalidThreshold
+                );
+                require!(signers.len() <= self.max_signers as usize, VaultError::TooManySigners);
+                require!(
+                    self.approval_tiers.iter().all(|tier| tier.threshold as usize <= signers.len() + 1),
+                    VaultError::InvalidThreshold
+                );
+            },
+            TransactionType::SetApprovalTiers { tiers } => {
+                require!(tiers.len() <= MAX_APPROVAL_TIERS, VaultError::InvalidApprovalTiers);
+                require!(
+                    tiers.windows(2).all(|pair| pair[0].max_amount < pair[1].max_amount),
+                    VaultError::InvalidApprovalTiers
+                );
+                require!(
+                    tiers.iter().all(|tier| {
+                        tier.threshold > 0 && tier.threshold as usize <= self.multisig_signers.len() + 1
+                    }),
+                    VaultError::InvalidThreshold
+                );
+            },
+            _ => {},
+        }
+        
+        Ok(())
+    }
+
+    /**
+     * 所有者を変更し、以前の所有者に紐づく設定を解除します。
+     * 
+     * 保留中の所有権譲渡とリカバリーを取り消し、委任者を削除します。
+     * 署名者設定のバージョンを更新するため、以前の所有者の承認を含む保留中のトランザクションは実行できなくなります。
+     * 新しい所有者の活動時刻として、相続のタイマーもリセットします。
+     * 
+     * @param new_owner - 新しい所有者のアドレス
+     * @param now - 現在のUNIXタイムスタンプ
+     */
+    pub fn rotate_owner(&mut self, new_owner: Pubkey, now: u64) {
+        self.owner = new_owner;
+        self.last_owner_activity = now;
+        // Clear pending transfer
+        self.transfer_ownership_to = None;
+        self.pending_recovery = None;
+        // Clear delegates as they were for the previous owner
+        self.delegates.clear();
+        // Proposals approved by the previous owner must not execute
+        self.config_version = self.config_version.wrapping_add(1);
+    }
+
+    /**
+     * 指定したミントのトークンを保管している金庫のトークンアカウントを返します。
+     * 
+     * @param mint - ミントのアドレス
+     * @return Option<Pubkey> - 初期化時のトークンアカウントまたは登録済みのトークンアカウント
+     */
+    pub fn token_account_for(&self, mint: &Pubkey) -> Option<Pubkey> {
+        if *mint == self.mint {
+            return Some(self.token_account);
+        }
+        self.mints.iter()
+            .find(|record| record.mint == *mint)
+            .map(|record| record.token_account)
+    }
+
+    /**
+     * 指定したミントの1回の引き出し上限を返します。
+     * 
+     * @param mint - ミントのアドレス
+     * @return u64 - 引き出し上限（登録されていないミントは0）
+     */
+    pub fn withdrawal_limit(&self, mint: &Pubkey) -> u64 {
+        if *mint == self.mint {
+            return self.max_withdrawal_limit;
+        }
+        self.mints.iter()
+            .find(|record| record.mint == *mint)
+            .map_or(0, |record| record.max_withdrawal_limit)
+    }
+
+    /**
+     * 委任者の引き出し権限を確認し、金額を委任者の引き出し枠から差し引きます。
+     * 
+     * @param delegate - 委任者のアドレス
+     * @param amount - 引き出す金額
+     * @param now - 現在のUNIXタイムスタンプ
+     * @return Result<()> - 委任者でない場合や引き出し枠を超える場合はエラー
+     */
+    pub fn spend_delegate_allowance(&mut self, delegate: Pubkey, amount: u64, now: u64) -> Result<()> {
+        self.delegates.iter_mut()
+            .find(|record| record.delegate == delegate)
+            .ok_or(VaultError::Unauthorized)?
+            .spend(amount, DELEGATE_PERMISSION_WITHDRAW, now)
+    }
+
+    /**
+     * 所有者の活動を記録し、相続のタイマーをリセットします。
+     * 
+     * @param now - 現在のUNIXタイムスタンプ
+     */
+    pub fn record_owner_activity(&mut self, now: u64) {
+        self.last_owner_activity = now;
+    }
+
+    /**
+     * 相続を請求できる状態かを判定します。
+     * 
+     * @param now - 現在のUNIXタイムスタンプ
+     * @return bool - 受益者が設定され、所有者が一定期間活動していない場合はtrue
+     */
+    pub fn is_inheritable(&self, now: u64) -> bool {
+        self.beneficiary.is_some()
+            && self.inactivity_period != 0
+            && now >= self.last_owner_activity.saturating_add(self.inactivity_period)
+    }
+
+    /**
+     * ロックモードに従ってタイムロックの期限を更新します。
+     * 
+     * @param lock_until - 新しいロック期限のUNIXタイムスタンプ
+     * @return Result<()> - ExtendOnlyモードで期限を短縮しようとした場合はエラー
+     */
+    pub fn set_lock_until(&mut self, lock_until: u64) -> Result<()> {
+        if self.lock_mode == LockMode::ExtendOnly {
+            require!(lock_until >= self.lock_until, VaultError::LockCannotBeShortened);
+        }
+        
+        self.lock_until = lock_until;
+        
+        Ok(())
+    }
+
+    /**
+     * 権利確定スケジュールに確保された金額を除いた残高から引き出せるかを確認します。
+     * 
+     * @param balance - 金庫のトークンアカウントの残高
+     * @param amount - 引き出す金額
+     * @return Result<()> - 確保されていない残高が不足する場合はエラー
+     */
+    pub fn check_unreserved(&self, balance: u64, amount: u64) -> Result<()> {
+        require!(
+            amount <= balance.saturating_sub(self.reserved_amount),
+            VaultError::InsufficientUnreservedBalance
+        );
+        
+        Ok(())
+    }
+
+    /**
+     * 引き出し金額に応じて必要な署名数を返します。
+     * 
+     * @param amount - 引き出す金額
+     * @return u8 - 該当する承認段階の署名数、該当しない場合はmultisig_threshold
+     */
+    pub fn withdrawal_threshold(&self, amount: u64) -> u8 {
+        self.approval_tiers.iter()
+            .find(|tier| amount <= tier.max_amount)
+            .map_or(self.multisig_threshold, |tier| tier.threshold)
+    }
+
+    /**
+     * 現在有効な許可リストの設定を返します。
+     * 
+     * 待機中の緩和は、待機時間が経過した時点で反映されたものとして扱います。
+     * 
+     * @param now - 現在のUNIXタイムスタンプ
+     * @return (bool, u64) - 許可リストが有効かどうかと、送金先が有効になるまでの待機時間
+     */
+    pub fn allowlist_settings(&self, now: u64) -> (bool, u64) {
+        match &self.pending_allowlist {
+            Some(pending) if now >= pending.active_at => (pending.enabled, pending.delay),
+            _ => (self.allowlist_enabled, self.allowlist_delay),
+        }
+    }
+
+    /**
+     * 送金先が許可リストに含まれ、有効になっているかを確認します。
+     * 
+     * トークンアカウントのアドレス、またはその所有者のアドレスのどちらかが登録されていれば許可されます。
+     * 
+     * @param destination - 送金先のトークンアカウント
+     * @param now - 現在のUNIXタイムスタンプ
+     * @return Result<()> - 許可されていない場合はエラー
+     */
+    pub fn check_destination(&self, destination: &InterfaceAccount<TokenAccount>, now: u64) -> Result<()> {
+        let (enabled, _) = self.allowlist_settings(now);
+        if !enabled {
+            return Ok(());
+        }
+        
+        let allowed = self.allowlist.iter().any(|entry| {
+            (entry.address == destination.key() || entry.address == destination.owner)
+                && now >= entry.active_at
+        });
+        require!(allowed, VaultError::DestinationNotAllowed);
+        
+        Ok(())
+    }
+
+    /**
+     * 引き出し枠の1区間の長さを計算します。
+     * 
+     * 期間の制限がない場合は使用しません。
+     * 
+     * @return u64 - 区間の長さ（秒）
+     */
+    fn window_bucket_length(&self) -> u64 {
+        self.window_length.div_ceil(WINDOW_BUCKETS as u64 - 1)
+    }
+
+    /**
+     * 直近の期間に引き出された合計金額を計算します。
+     * 
+     * 引き出しは記録した区間が期間の長さ以上過ぎるまで合計に含まれるため、
+     * どの時点から数えても期間内の引き出しが枠を超えることはありません。
+     * 
+     * @param now - 現在のUNIXタイムスタンプ
+     * @return u64 - 直近の期間に引き出された合計金額
+     */
+    pub fn window_spent_total(&self, now: u64) -> u64 {
+        if self.window_length == 0 {
+            return 0;
+        }
+        
+        // Buckets stay counted until a whole window has passed since their end
+        let bucket_length = self.window_bucket_length();
+        let span = self.window_length.div_ceil(bucket_length);
+        let current = now / bucket_length;
+        (0..=span)
+            .filter_map(|offset| self.window_bucket.checked_sub(offset))
+            .filter(|bucket| current.saturating_sub(*bucket) <= span)
+            .map(|bucket| self.window_spent[(bucket % WINDOW_BUCKETS as u64) as usize])
+            .fold(0u64, |total, spent| total.saturating_add(spent))
+    }
+
+    /**
+     * 直近の期間の残り引き出し枠を計算します。
+     * 
+     * @param now - 現在のUNIXタイムスタンプ
+     * @return u64 - 残り引き出し枠（期間の制限がない場合はu64::MAX）
+     */
+    pub fn remaining_window_budget(&self, now: u64) -> u64 {
+        if self.window_length == 0 {
+            return u64::MAX;
+        }
+        
+        self.window_limit.saturating_sub(self.window_spent_total(now))
+    }
+
+    /**
+     * 引き出し金額を現在の区間に記録し、直近の期間の引き出し枠から差し引きます。
+     * 
+     * @param amount - 引き出す金額
+     * @param now - 現在のUNIXタイムスタンプ
+     * @return Result<()> - 残り引き出し枠を超える場合はエラー
+     */
+    pub fn consume_window_budget(&mut self, amount: u64, now: u64) -> Result<()> {
+        if self.window_length == 0 {
+            return Ok(());
+        }
+        
+        let remaining = self.remaining_window_budget(now);
+        if amount > remaining {
+            msg!("Remaining withdrawal budget for this window: {}", remaining);
+            return Err(VaultError::ExceedsWindowBudget.into());
+        }
+        
+        // Clear the buckets skipped since the last withdrawal before reusing them
+        let current = now / self.window_bucket_length();
+        if current > self.window_bucket {
+            let skipped = (current - self.window_bucket).min(WINDOW_BUCKETS as u64);
+            for bucket in (current + 1 - skipped)..=current {
+                self.window_spent[(bucket % WINDOW_BUCKETS as u64) as usize] = 0;
+            }
+            self.window_bucket = current;
+        }
+        let slot = (self.window_bucket % WINDOW_BUCKETS as u64) as usize;
+        self.window_spent[slot] = self.window_spent[slot].saturating_add(amount);
+        
+        Ok(())
+    }
+
+    /**
+     * 現在時刻に作成される保留中トランザクションの有効期限を計算します。
+     * 
+     * @param now - 現在のUNIXタイムスタンプ
+     * @return u64 - 有効期限のUNIXタイムスタンプ（0は期限なし）
+     */
+    pub fn transaction_expiry(&self, now: u64) -> u64 {
+        if self.transaction_ttl == 0 {
+            0
+        } else {
+            now.saturating_add(self.transaction_ttl)
+        }
+    }
+}
+
+/**
+ * 保留中トランザクションのデータ構造体
+ * 
+ * 多重署名が必要なトランザクションの情報を保存します。
+ * トランザクションの種類、金額、送信先、署名者リストなどを含みます。
+ * 各トランザクションは金庫とIDから派生する個別のPDAに保存されます。
+ */
+#[account]
+pub struct PendingTransaction {
+    pub vault: Pubkey,                  // 対象の金庫
+    pub id: u64,                        // トランザクションID
+    pub proposer: Pubkey,               // 提案者（閉鎖時にレントを受け取る）
+    pub transaction_type: TransactionType, // トランザクションの種類
+    pub amount: u64,                    // 引き出し量（引き出しの場合）
+    pub destination: Pubkey,            // 送金先（引き出しの場合）
+    pub new_owner: Option<Pubkey>,      // 所有権譲渡先（TransferOwnershipの場合のみ使用）
+    pub signers: Vec<Pubkey>,           // 署名者リスト
+    pub rejections: Vec<Pubkey>,        // 拒否した署名者リスト
+    pub threshold: u8,                  // 実行に必要な承認数（作成時に決定）
+    pub status: TransactionStatus,      // 処理状況
+    pub created_at: u64,                // 作成時刻
+    pub expires_at: u64,                // 有効期限（0は期限なし）
+    pub config_version: u32,            // 作成時の署名者設定のバージョン
+    pub bump: u8,                       // PDAのバンプシード
+}
+
+impl PendingTransaction {
+    /**
+     * 保留中トランザクションのアカウントに必要なバイト数を計算します。
+     * 
+     * @param max_signers - 追加署名者の最大数（所有者の署名分は別途加算されます）
+     * @return usize - アカウントディスクリミネータを含むバイト数
+     */
+    pub fn space(max_signers: u16) -> usize {
+        8 +                                     // ディスクリミネータ
+        32 + 8 + 32 +                           // vault, id, proposer
+        TransactionType::space(max_signers) +   // transaction_type
+        8 + 32 + 33 +                           // amount, destination, new_owner
+        4 + ((max_signers as usize + 1) * 32) + // signers（所有者を含む）
+        4 + ((max_signers as usize + 1) * 32) + // rejections（所有者を含む）
+        1 +                                     // threshold
+        1 + 8 + 8 + 4 + 1                       // status, created_at, expires_at, config_version, bump
+    }
+
+    /**
+     * 有効期限を過ぎているかどうかを判定します。
+     * 
+     * @param now - 現在のUNIXタイムスタンプ
+     * @return bool - 期限切れの場合はtrue
+     */
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+}
+
+/**
+ * 引き出し予約のデータ構造体
+ * 
+ * 待機時間の経過後に実行される引き出しの情報を保存します。
+ * 各予約は金庫とIDから派生する個別のPDAに保存され、実行またはキャンセル時に閉鎖されます。
+ */
+#[account]
+pub struct WithdrawalRequest {
+    pub vault: Pubkey,                  // 対象の金庫
+    pub id: u64,                        // 引き出し予約ID
+    pub requester: Pubkey,              // 予約者（閉鎖時にレントを受け取る）
+    pub amount: u64,                    // 引き出し量
+    pub destination: Pubkey,            // 送金先のトークンアカウント
+    pub requested_at: u64,              // 予約時刻
+    pub executable_at: u64,             // 実行可能になる時刻
+    pub bump: u8,                       // PDAのバンプシード
+}
+
+impl WithdrawalRequest {
+    pub const SPACE: usize = 8 + 32 + 8 + 32 + 8 + 32 + 8 + 8 + 1;
+}
+
+/**
+ * 権利確定スケジュールのデータ構造体
+ * 
+ * 受益者に対して線形に権利確定するトークンの情報を保存します。
+ * 各スケジュールは金庫とIDから派生する個別のPDAに保存されます。
+ */
+#[account]
+pub struct VestingSchedule {
+    pub vault: Pubkey,                  // 対象の金庫
+    pub id: u64,                        // スケジュールID
+    pub beneficiary: Pubkey,            // 受益者
+    pub funder: Pubkey,                 // 作成者（閉鎖時にレントを受け取る）
+    pub start: u64,                     // 権利確定の開始時刻
+    pub cliff: u64,                     // クリフ時刻（この時刻までは何も確定しない）
+    pub end: u64,                       // 全額が確定する時刻
+    pub total_amount: u64,              // 権利確定する総額（取り消し後は確定済みの金額）
+    pub released_amount: u64,           // 支払い済みの金額
+    pub revocable: bool,                // 所有者が未確定分を取り消せるかどうか
+    pub revoked: bool,                  // 取り消し済みかどうか
+    pub bump: u8,                       // PDAのバンプシード
+}
+
+impl VestingSchedule {
+    pub const SPACE: usize = 8 + 32 + 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 1 + 1;
+
+    /**
+     * 指定した時刻までに権利確定した金額を計算します。
+     * 
+     * @param now - 現在のUNIXタイムスタンプ
+     * @return u64 - 権利確定した金額（支払い済みの分を含む）
+     */
+    pub fn vested_amount(&self, now: u64) -> u64 {
+        // A revoked schedule has already been cut down to what vested
+        if self.revoked || now >= self.end {
+            return self.total_amount;
+        }
+        if now < self.cliff {
+            return 0;
+        }
+        
+        let elapsed = (now - self.start) as u128;
+        let duration = (self.end - self.start) as u128;
+        (self.total_amount as u128 * elapsed / duration) as u64
     }
 }
 
 /**
- * 引き出し実行ヘルパー関数
+ * 定期支払いのデータ構造体
  * 
- * 金庫のトークンアカウントからユーザーのトークンアカウントにトークンを移動する
- * 実際の転送処理を行います。金庫のPDA（Program Derived Address）を使用して
- * 署名し、トークン転送のCPI（Cross-Program Invocation）を実行します。
+ * 一定期間ごとに受取人へ支払うトークンの情報を保存します。
+ * 各定期支払いは金庫とIDから派生する個別のPDAに保存されます。
+ */
+#[account]
+pub struct PaymentStream {
+    pub vault: Pubkey,                  // 対象の金庫
+    pub id: u64,                        // 定期支払いID
+    pub recipient: Pubkey,              // 受取人のトークンアカウント
+    pub funder: Pubkey,                 // 作成者（閉鎖時にレントを受け取る）
+    pub amount_per_period: u64,         // 1期間あたりの支払額
+    pub period_length: u64,             // 期間の長さ（秒）
+    pub start: u64,                     // 支払いの開始時刻
+    pub end: u64,                       // 支払いの終了時刻（0は終了なし）
+    pub last_claimed: u64,              // 支払い済みの期間の終了時刻
+    pub bump: u8,                       // PDAのバンプシード
+}
+
+impl PaymentStream {
+    pub const SPACE: usize = 8 + 32 + 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    /**
+     * 前回の支払い以降に経過した期間数を計算します。
+     * 
+     * @param now - 現在のUNIXタイムスタンプ
+     * @return u64 - 支払いが発生した期間数
+     */
+    pub fn accrued_periods(&self, now: u64) -> u64 {
+        let until = if self.end == 0 { now } else { now.min(self.end) };
+        until.saturating_sub(self.last_claimed) / self.period_length
+    }
+}
+
+/**
+ * 監査ログのデータ構造体
  * 
- * @param vault - 金庫アカウント情報
- * @param vault_token_account - 金庫のトークンアカウント情報
- * @param destination_token_account - 宛先のトークンアカウント情報
- * @param token_program - SPLトークンプログラム情報
- * @param amount - 引き出すトークンの量
- * @param bump - 金庫PDAのバンプシード
- * @return Result<()> - 操作の成功または失敗
+ * 金庫ごとに1つ、金庫から派生するPDAに保存される固定サイズのリングバッファです。
+ * ゼロコピーで読み書きするため、エントリ数が多くてもデシリアライズのコストがかかりません。
  */
-fn execute_withdraw<'info>(
-    vault: AccountInfo<'info>,
-    vault_token_account: AccountInfo<'info>,
-    destination_token_account: AccountInfo<'info>,
-    token_program: AccountInfo<'info>,
-    amount: u64,
-    bump: u8,
-) -> Result<()> {
-    let vault_data = Vault::try_from_slice(&vault.try_borrow_data()?)?;
-    
-    // Create signer seeds for PDA
-    let seeds = &[
-        b"vault".as_ref(),
-        vault_data.owner.as_ref(),
-        &[bump],
-    ];
-    let signer = &[&seeds[..]];
-    
-    // Execute the transfer
-    let cpi_accounts = Transfer {
-        from: vault_token_account,
-        to: destination_token_account,
-        authority: vault,
-    };
-    
-    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer);
-    token::transfer(cpi_ctx, amount)?;
-    
-    Ok(())
+#[account(zero_copy)]
+pub struct AuditLog {
+    pub vault: Pubkey,                  // 対象の金庫
+    pub head: u64,                      // これまでに記録されたエントリの総数
+    pub entries: [AuditEntry; AUDIT_LOG_CAPACITY], // エントリのリングバッファ
+}
+
+impl AuditLog {
+    pub const SPACE: usize = 8 + 32 + 8 + AUDIT_LOG_CAPACITY * AuditEntry::SPACE;
+
+    /**
+     * エントリを追記します。容量を超えた場合は最も古いエントリを上書きします。
+     * 
+     * @param entry - 追記するエントリ
+     */
+    pub fn push(&mut self, entry: AuditEntry) {
+        let index = (self.head % AUDIT_LOG_CAPACITY as u64) as usize;
+        self.entries[index] = entry;
+        self.head += 1;
+    }
+
+    /**
+     * 保持されている最も古いエントリの通し番号を返します。
+     * 
+     * @return u64 - 最も古いエントリの通し番号
+     */
+    pub fn first_sequence(&self) -> u64 {
+        self.head.saturating_sub(AUDIT_LOG_CAPACITY as u64)
+    }
+
+    /**
+     * 指定した通し番号のエントリを読み取ります。
+     * 
+     * @param sequence - 保持されている範囲内のエントリの通し番号
+     * @return AuditRecord - 通し番号付きのエントリ
+     */
+    pub fn record(&self, sequence: u64) -> AuditRecord {
+        let entry = &self.entries[(sequence % AUDIT_LOG_CAPACITY as u64) as usize];
+        AuditRecord {
+            sequence,
+            action: entry.action,
+            actor: entry.actor,
+            counterparty: entry.counterparty,
+            amount: entry.amount,
+            slot: entry.slot,
+        }
+    }
+}
+
+/**
+ * トランザクション種類の列挙型
+ * 
+ * 保留中トランザクションの種類を定義します。
+ * 設定変更の種類は、承認後に反映する設定値を保持します。
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum TransactionType {
+    Withdraw,           // トークン引き出し
+    TransferOwnership,  // 所有権譲渡
+    CloseVault,         // 金庫の閉鎖
+    SetTimelock { lock_duration: u64 },                 // タイムロックの設定
+    AddDelegate { record: DelegateRecord },             // 委任者の追加
+    SetMultisig { threshold: u8, signers: Vec<Pubkey> }, // 多重署名の設定
+    SetWithdrawalLimit { limit: u64 },                  // 引き出し制限の設定
+    SetWithdrawalWindow { window_length: u64, window_limit: u64 }, // 期間ごとの引き出し制限の設定
+    AddAllowlistEntry { address: Pubkey },              // 許可リストへの送金先の追加
+    SetAllowlist { enabled: bool, delay: u64 },         // 許可リストの設定
+    SetWithdrawalDelay { delay: u64 },                  // 引き出し待機時間の設定
+    SetApprovalTiers { tiers: Vec<ApprovalTier> },      // 承認段階の設定
+    SetTimelockUntil { unlock_at: u64 },                // 期限指定タイムロックの設定
+    SetLockMode { mode: LockMode },                     // ロックモードの設定
+    SetGuardians { guardians: Vec<Pubkey> },            // ガーディアンの設定
+    Unpause,                                            // 一時停止の解除
+    SetRecovery { threshold: u8, delay: u64 },          // リカバリーの設定
+    SetInheritance { beneficiary: Option<Pubkey>, inactivity_period: u64 }, // 相続の設定
+    SetMintWithdrawalLimit { mint: Pubkey, limit: u64 }, // ミントごとの引き出し制限の設定
+    SetTransactionTtl { ttl: u64 },                     // 保留中トランザクションの有効期間の設定
+}
+
+impl TransactionType {
+    /**
+     * シリアライズ後の最大バイト数を計算します。
+     * 
+     * @param max_signers - 追加署名者の最大数
+     * @return usize - 最も大きい種類のバイト数
+     */
+    pub fn space(max_signers: u16) -> usize {
+        let set_multisig = 1 + 4 + (max_signers as usize * 32); // threshold, signers
+        let set_approval_tiers = 4 + (MAX_APPROVAL_TIERS * ApprovalTier::SPACE); // tiers
+        let set_guardians = 4 + (MAX_GUARDIANS * 32);           // guardians
+        1 + set_multisig                                        // variant + largest payload
+            .max(DelegateRecord::SPACE)
+            .max(set_approval_tiers)
+            .max(set_guardians)
+    }
+}
+
+/**
+ * 委任者の記録構造体
+ * 
+ * 委任されたアドレスごとの権限、引き出し枠、有効期限を保持します。
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct DelegateRecord {
+    pub delegate: Pubkey,               // 委任されたアドレス
+    pub allowance: u64,                 // 期間内の引き出し枠（u64::MAXは制限なし）
+    pub refill_period: u64,             // 引き出し枠の補充期間（秒、0は補充なし）
+    pub spent: u64,                     // 現在の期間に使用済みの金額
+    pub period_start: u64,              // 現在の期間の開始UNIXタイムスタンプ
+    pub expires_at: u64,                // 委任の有効期限のUNIXタイムスタンプ（0は期限なし）
+    pub permissions: u8,                // 許可された操作の権限フラグ
+}
+
+impl DelegateRecord {
+    pub const SPACE: usize = 32 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    /**
+     * 委任の有効期限が過ぎているかを判定します。
+     * 
+     * @param now - 現在のUNIXタイムスタンプ
+     * @return bool - 有効期限を過ぎている場合はtrue
+     */
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    /**
+     * 現在の期間の残り引き出し枠を計算します。
+     * 
+     * @param now - 現在のUNIXタイムスタンプ
+     * @return u64 - 残り引き出し枠
+     */
+    pub fn remaining_allowance(&self, now: u64) -> u64 {
+        // The allowance is refilled once the period has passed
+        if self.refill_period != 0 && now >= self.period_start.saturating_add(self.refill_period) {
+            return self.allowance;
+        }
+        
+        self.allowance.saturating_sub(self.spent)
+    }
+
+    /**
+     * 委任が有効期限内で、指定した権限を持っているかを確認します。
+     * 
+     * @param permission - 必要な権限フラグ
+     * @param now - 現在のUNIXタイムスタンプ
+     * @return Result<()> - 許可されている場合はOk、そうでない場合はエラー
+     */
+    pub fn authorize(&self, permission: u8, now: u64) -> Result<()> {
+        require!(!self.is_expired(now), VaultError::DelegateExpired);
+        require!(self.permissions & permission != 0, VaultError::DelegatePermissionDenied);
+        Ok(())
+    }
+
+    /**
+     * 権限と有効期限を確認し、金額を引き出し枠から差し引きます。
+     * 
+     * @param amount - 使用する金額
+     * @param permission - 必要な権限フラグ
+     * @param now - 現在のUNIXタイムスタンプ
+     * @return Result<()> - 操作の成功または失敗
+     */
+    pub fn spend(&mut self, amount: u64, permission: u8, now: u64) -> Result<()> {
+        self.authorize(permission, now)?;
+        require!(amount <= self.remaining_allowance(now), VaultError::ExceedsDelegateAllowance);
+        
+        // An unlimited allowance does not need tracking
+        if self.allowance == u64::MAX {
+            return Ok(());
+        }
+        
+        // Start a new period if the previous one has ended
+        if self.refill_period != 0 && now >= self.period_start.saturating_add(self.refill_period) {
+            self.period_start = now;
+            self.spent = 0;
+        }
+        self.spent += amount;
+        
+        Ok(())
+    }
+}
+
+/**
+ * 許可リストの登録内容構造体
+ * 
+ * 引き出しの送金先として許可されたアドレスと、有効になる時刻を保持します。
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct AllowlistEntry {
+    pub address: Pubkey,                // トークンアカウントまたはウォレットのアドレス
+    pub active_at: u64,                 // 有効になるUNIXタイムスタンプ
+}
+
+impl AllowlistEntry {
+    pub const SPACE: usize = 32 + 8;
+}
+
+/**
+ * 待機中の許可リスト変更構造体
+ * 
+ * 許可リストの無効化や待機時間の短縮は、変更前の待機時間が経過するまで反映されません。
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct PendingAllowlistChange {
+    pub enabled: bool,                  // 反映後に許可リストを有効にするかどうか
+    pub delay: u64,                     // 反映後の待機時間（秒）
+    pub active_at: u64,                 // 反映されるUNIXタイムスタンプ
+}
+
+impl PendingAllowlistChange {
+    pub const SPACE: usize = 1 + 8 + 8;
+}
+
+/**
+ * 進行中のリカバリー構造体
+ * 
+ * ガーディアンが提案した新しい所有者と、集まった承認を保持します。
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct PendingRecovery {
+    pub new_owner: Pubkey,              // 新しい所有者
+    pub approvals: Vec<Pubkey>,         // 承認したガーディアンのリスト
+    pub executable_at: u64,             // 確定できるようになる時刻（0は承認数不足）
+}
+
+impl PendingRecovery {
+    pub const SPACE: usize = 32 + 4 + (MAX_GUARDIANS * 32) + 8;
+
+    /**
+     * 必要な承認数に達した場合に待機時間を開始します。
+     * 
+     * @param threshold - リカバリーに必要な承認数
+     * @param delay - 待機時間（秒）
+     * @param now - 現在のUNIXタイムスタンプ
+     */
+    pub fn start_delay_if_approved(&mut self, threshold: u8, delay: u64, now: u64) {
+        if self.executable_at == 0 && self.approvals.len() >= threshold as usize {
+            self.executable_at = now.saturating_add(delay).max(1);
+        }
+    }
+}
+
+/**
+ * 監査ログのエントリ構造体
+ * 
+ * ゼロコピーのため、フィールドは境界に揃えて配置しています。
+ */
+#[zero_copy]
+pub struct AuditEntry {
+    pub slot: u64,                      // 記録されたスロット
+    pub amount: u64,                    // 操作した金額（設定変更では変更後の値）
+    pub actor: Pubkey,                  // 操作を行ったアドレス
+    pub counterparty: Pubkey,           // 操作の相手
+    pub action: u8,                     // 操作の種類（AUDIT_ACTION_*）
+    pub _padding: [u8; 7],              // 境界揃えのための予約領域
+}
+
+impl AuditEntry {
+    pub const SPACE: usize = 8 + 8 + 32 + 32 + 1 + 7;
+}
+
+/**
+ * 監査ログの読み取り結果
+ * 
+ * read_audit_log命令の戻り値として、エントリに通し番号を付けて返します。
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct AuditRecord {
+    pub sequence: u64,                  // エントリの通し番号
+    pub action: u8,                     // 操作の種類（AUDIT_ACTION_*）
+    pub actor: Pubkey,                  // 操作を行ったアドレス
+    pub counterparty: Pubkey,           // 操作の相手
+    pub amount: u64,                    // 操作した金額
+    pub slot: u64,                      // 記録されたスロット
+}
+
+/**
+ * ミント登録簿のエントリ構造体
+ * 
+ * 追加で登録したミントと、そのトークンを保管する金庫のトークンアカウント、引き出し上限を保持します。
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct MintRecord {
+    pub mint: Pubkey,                   // ミントのアドレス
+    pub token_account: Pubkey,          // 金庫とミントから派生するトークンアカウント
+    pub max_withdrawal_limit: u64,      // 1回の取引で引き出せる最大金額
+}
+
+impl MintRecord {
+    pub const SPACE: usize = 32 + 32 + 8;
+}
+
+/**
+ * 承認段階構造体
+ * 
+ * 引き出し金額の上限と、その金額までの引き出しに必要な署名数を保持します。
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct ApprovalTier {
+    pub max_amount: u64,                // この段階が適用される引き出し金額の上限（この値を含む）
+    pub threshold: u8,                  // 必要な署名者数
+}
+
+impl ApprovalTier {
+    pub const SPACE: usize = 8 + 1;
+}
+
+/**
+ * 相続請求方法の列挙型
+ * 
+ * 受益者が金庫を請求する際の受け取り方を表します。
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Copy)]
+pub enum InheritanceClaim {
+    TakeOwnership,      // 所有権を引き継ぐ
+    Sweep,              // 残高を受け取る
+}
+
+/**
+ * ロックモードの列挙型
+ * 
+ * タイムロックの期限を変更する際の制約を表します。
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Copy)]
+pub enum LockMode {
+    Flexible,           // 期限の延長・短縮が可能
+    ExtendOnly,         // 期限の延長のみ可能（元に戻せない）
+}
+
+/**
+ * トランザクション処理状況の列挙型
+ * 
+ * 保留中トランザクションが承認待ちか、処理済みかを表します。
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Copy)]
+pub enum TransactionStatus {
+    Pending,            // 承認待ち
+    Executed,           // 実行済み
+    Cancelled,          // キャンセル済み
+    Rejected,           // 拒否済み
+}
+
+/**
+ * 金庫初期化イベント
+ * 
+ * initialize命令で金庫が作成されたときに発行されます。
+ */
+#[event]
+pub struct VaultInitialized {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub owner: Pubkey,                  // 所有者のアドレス
+    pub vault_id: u64,                  // 作成者ごとの金庫ID
+    pub token_account: Pubkey,          // 金庫のトークンアカウント
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * 預け入れイベント
+ */
+#[event]
+pub struct Deposited {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub depositor: Pubkey,              // 預け入れたアドレス
+    pub mint: Pubkey,                   // 預け入れたトークンのミント
+    pub amount: u64,                    // 預け入れた量
+    pub received: u64,                  // 転送手数料を差し引いて金庫が受け取った量
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * 引き出しイベント
+ * 
+ * 即時の引き出し、承認済みトランザクション、引き出し予約の実行、相続による残高の受け取り、
+ * 定期支払いと権利確定分の支払い、金庫の閉鎖時の残高の移動で発行されます。
+ */
+#[event]
+pub struct Withdrawn {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub actor: Pubkey,                  // 引き出しを要求したアドレス
+    pub destination: Pubkey,            // 送金先のトークンアカウント
+    pub amount: u64,                    // 引き出した量
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * 引き出し予約イベント
+ */
+#[event]
+pub struct WithdrawalRequested {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub request_id: u64,                // 引き出し予約ID
+    pub requester: Pubkey,              // 予約したアドレス
+    pub destination: Pubkey,            // 送金先のトークンアカウント
+    pub amount: u64,                    // 引き出す量
+    pub executable_at: u64,             // 実行できるようになるUNIXタイムスタンプ
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * 引き出し予約キャンセルイベント
+ */
+#[event]
+pub struct WithdrawalCancelled {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub request_id: u64,                // 引き出し予約ID
+    pub actor: Pubkey,                  // キャンセルしたアドレス
+    pub amount: u64,                    // 取り消された引き出しの量
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * トランザクション提案イベント
+ * 
+ * 多重署名の保留中トランザクションが作成されたときに発行されます。
+ */
+#[event]
+pub struct TransactionProposed {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub tx_id: u64,                     // トランザクションID
+    pub proposer: Pubkey,               // 提案者のアドレス
+    pub transaction_type: TransactionType, // トランザクションの種類
+    pub amount: u64,                    // 引き出しの場合の金額
+    pub threshold: u8,                  // 必要な署名数
+    pub expires_at: u64,                // 有効期限のUNIXタイムスタンプ
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * トランザクション承認イベント
+ */
+#[event]
+pub struct TransactionApproved {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub tx_id: u64,                     // トランザクションID
+    pub signer: Pubkey,                 // 承認したアドレス
+    pub approvals: u8,                  // 現在の承認数
+    pub threshold: u8,                  // 必要な署名数
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * トランザクション実行イベント
+ * 
+ * execute_transaction命令は誰でも実行できるため、提案者を記録します。
+ */
+#[event]
+pub struct TransactionExecuted {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub tx_id: u64,                     // トランザクションID
+    pub proposer: Pubkey,               // 提案者のアドレス
+    pub approvals: u8,                  // 実行時の承認数
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * 承認取り消しイベント
+ */
+#[event]
+pub struct ApprovalRevoked {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub tx_id: u64,                     // トランザクションID
+    pub signer: Pubkey,                 // 承認を取り消したアドレス
+    pub approvals: u8,                  // 現在の承認数
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * トランザクション拒否イベント
+ * 
+ * 反対票が投じられるたびに発行されます。必要な署名数に届かなくなった場合はrejectedがtrueになります。
+ */
+#[event]
+pub struct TransactionRejected {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub tx_id: u64,                     // トランザクションID
+    pub signer: Pubkey,                 // 反対票を投じたアドレス
+    pub rejections: u8,                 // 現在の反対票の数
+    pub rejected: bool,                 // トランザクションが拒否済みになったかどうか
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * トランザクションキャンセルイベント
+ */
+#[event]
+pub struct TransactionCancelled {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub tx_id: u64,                     // トランザクションID
+    pub actor: Pubkey,                  // キャンセルしたアドレス
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
 }
 
 /**
- * 金庫初期化用のアカウント構造体
+ * トランザクション期限切れイベント
  * 
- * 金庫の初期化時に必要な全てのアカウント情報を定義します。
- * この構造体はinitialize命令の実行時に使用されます。
+ * expire_transaction命令で保留中トランザクションが閉鎖されたときに発行されます。
  */
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        init,
-        payer = owner,
-        space = 8 + 32 + 32 + 1 + 8 + 4 + (10 * 32) + 1 + 4 + (5 * 32) + 4 + (10 * (8 + 1 + 8 + 32 + 4 + (5 * 32) + 1 + 8 + 1 + 32)) + 8 + 33, // Added space for ownership transfer
-        seeds = [b"vault", owner.key().as_ref()],
-        bump
-    )]
-    pub vault: Account<'info, Vault>,
-    
-    #[account(
-        init,
-        payer = owner,
-        token::mint = mint,
-        token::authority = vault,
-    )]
-    pub vault_token_account: Account<'info, TokenAccount>,
-    
-    pub mint: Account<'info, token::Mint>,
-    
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+#[event]
+pub struct TransactionExpired {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub tx_id: u64,                     // トランザクションID
+    pub stale: bool,                    // 署名者設定の変更により無効になったかどうか
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
 }
 
 /**
- * トークン預け入れ用のアカウント構造体
+ * タイムロック変更イベント
  * 
- * トークンを金庫に預け入れる際に必要な全てのアカウント情報を定義します。
- * この構造体はdeposit命令の実行時に使用されます。
+ * ロック期限またはロックモードが変更されたときに発行されます。
  */
-#[derive(Accounts)]
-pub struct Deposit<'info> {
-    #[account(mut)]
-    pub vault: Account<'info, Vault>,
-    
-    #[account(
-        mut,
-        constraint = vault_token_account.key() == vault.token_account,
-    )]
-    pub vault_token_account: Account<'info, TokenAccount>,
-    
-    #[account(
-        mut,
-        constraint = user_token_account.owner == owner.key(),
-    )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct TimelockUpdated {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub actor: Pubkey,                  // 変更を提案したアドレス
+    pub lock_until: u64,                // 変更後のロック期限
+    pub lock_mode: LockMode,            // 変更後のロックモード
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
 }
 
 /**
- * トークン引き出し用のアカウント構造体
- * 
- * トークンを金庫から引き出す際に必要な全てのアカウント情報を定義します。
- * この構造体はwithdraw命令の実行時に使用されます。
+ * 委任者追加イベント
  */
-#[derive(Accounts)]
-pub struct Withdraw<'info> {
-    #[account(
-        mut,
-        seeds = [b"vault", vault.owner.as_ref()],
-        bump = vault.bump,
-    )]
-    pub vault: Account<'info, Vault>,
-    
-    #[account(
-        mut,
-        constraint = vault_token_account.key() == vault.token_account,
-    )]
-    pub vault_token_account: Account<'info, TokenAccount>,
-    
-    #[account(
-        mut,
-        constraint = user_token_account.owner == owner.key(),
-    )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct DelegateAdded {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub actor: Pubkey,                  // 変更を提案したアドレス
+    pub delegate: Pubkey,               // 委任されたアドレス
+    pub allowance: u64,                 // 引き出し枠
+    pub refill_period: u64,             // 引き出し枠の補充期間（秒）
+    pub expires_at: u64,                // 委任の有効期限
+    pub permissions: u8,                // 許可された操作の権限フラグ
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
 }
 
 /**
- * 残高確認用のアカウント構造体
- * 
- * 金庫内のトークン残高を確認する際に必要なアカウント情報を定義します。
- * この構造体はquery_balance命令の実行時に使用されます。
+ * 委任者削除イベント
  */
-#[derive(Accounts)]
-pub struct QueryBalance<'info> {
-    pub vault: Account<'info, Vault>,
-    
-    #[account(
-        constraint = token_account.key() == vault.token_account,
-    )]
-    pub token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct DelegateRemoved {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub actor: Pubkey,                  // 削除したアドレス
+    pub delegate: Pubkey,               // 削除された委任アドレス
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
 }
 
 /**
- * タイムロック設定用のアカウント構造体
+ * 多重署名設定変更イベント
  * 
- * 金庫にタイムロックを設定する際に必要なアカウント情報を定義します。
- * この構造体はset_timelock命令の実行時に使用されます。
+ * 署名者、閾値、または金額別の承認段階が変更されたときに発行されます。
  */
-#[derive(Accounts)]
-pub struct SetTimelock<'info> {
-    #[account(
-        mut,
-        seeds = [b"vault", owner.key().as_ref()],
-        bump = vault.bump,
-    )]
-    pub vault: Account<'info, Vault>,
-    
-    #[account(mut)]
-    pub owner: Signer<'info>,
+#[event]
+pub struct MultisigUpdated {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub actor: Pubkey,                  // 変更を提案したアドレス
+    pub threshold: u8,                  // 変更後の必要な署名者数
+    pub signers: Vec<Pubkey>,           // 変更後の署名者リスト
+    pub approval_tiers: Vec<ApprovalTier>, // 変更後の承認段階
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
 }
 
 /**
- * 委任者管理用のアカウント構造体
+ * 引き出し制限変更イベント
  * 
- * 委任者の追加・削除に必要なアカウント情報を定義します。
- * この構造体はadd_delegate命令とremove_delegate命令の実行時に使用されます。
+ * 1回の引き出し上限または期間ごとの引き出し枠が変更されたときに発行されます。
  */
-#[derive(Accounts)]
-pub struct ManageDelegate<'info> {
-    #[account(
-        mut,
-        seeds = [b"vault", owner.key().as_ref()],
-        bump = vault.bump,
-    )]
-    pub vault: Account<'info, Vault>,
-    
-    #[account(mut)]
-    pub owner: Signer<'info>,
+#[event]
+pub struct WithdrawalLimitUpdated {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub actor: Pubkey,                  // 変更を提案したアドレス
+    pub max_withdrawal_limit: u64,      // 変更後の1回の引き出し上限
+    pub window_length: u64,             // 変更後の期間の長さ（秒）
+    pub window_limit: u64,              // 変更後の期間ごとの引き出し枠
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
 }
 
 /**
- * 多重署名設定用のアカウント構造体
- * 
- * 多重署名（マルチシグ）設定に必要なアカウント情報を定義します。
- * この構造体はset_multisig命令の実行時に使用されます。
+ * ミント登録イベント
  */
-#[derive(Accounts)]
-pub struct SetMultisig<'info> {
-    #[account(
-        mut,
-        seeds = [b"vault", owner.key().as_ref()],
-        bump = vault.bump,
-    )]
-    pub vault: Account<'info, Vault>,
-    
-    #[account(mut)]
-    pub owner: Signer<'info>,
+#[event]
+pub struct MintRegistered {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub actor: Pubkey,                  // 登録したアドレス
+    pub mint: Pubkey,                   // 登録したミント
+    pub token_account: Pubkey,          // 作成されたトークンアカウント
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
 }
 
-#[derive(Accounts)]
-pub struct SetWithdrawalLimit<'info> {
-    #[account(
-        mut,
-        seeds = [b"vault", owner.key().as_ref()],
-        bump = vault.bump,
-    )]
-    pub vault: Account<'info, Vault>,
-    
-    #[account(mut)]
-    pub owner: Signer<'info>,
+/**
+ * ミント登録解除イベント
+ */
+#[event]
+pub struct MintUnregistered {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub actor: Pubkey,                  // 登録を解除したアドレス
+    pub mint: Pubkey,                   // 登録を解除したミント
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
 }
 
 /**
- * 所有権譲渡開始用のアカウント構造体
- * 
- * 所有権譲渡を開始する際に必要なアカウント情報を定義します。
- * この構造体はinitiate_ownership_transfer命令の実行時に使用されます。
+ * その他の設定変更イベント
  */
-#[derive(Accounts)]
-pub struct InitiateOwnershipTransfer<'info> {
-    #[account(
-        mut,
-        seeds = [b"vault", owner.key().as_ref()],
-        bump = vault.bump,
-    )]
-    pub vault: Account<'info, Vault>,
-    
-    #[account(mut)]
-    pub owner: Signer<'info>,
+#[event]
+pub struct ConfigChanged {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub actor: Pubkey,                  // 変更を提案したアドレス
+    pub change: TransactionType,        // 反映された設定変更
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
 }
 
 /**
- * 所有権譲渡承認用のアカウント構造体
- * 
- * 所有権譲渡を承認する際に必要なアカウント情報を定義します。
- * この構造体はaccept_ownership命令の実行時に使用されます。
+ * 所有権譲渡開始イベント
  */
-#[derive(Accounts)]
-pub struct AcceptOwnership<'info> {
-    #[account(
-        mut,
-        seeds = [b"vault", vault.owner.as_ref()],
-        bump = vault.bump,
-    )]
-    pub vault: Account<'info, Vault>,
-    
-    #[account(mut)]
-    pub new_owner: Signer<'info>,
+#[event]
+pub struct OwnershipTransferInitiated {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub owner: Pubkey,                  // 現在の所有者
+    pub new_owner: Pubkey,              // 譲渡先のアドレス
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
 }
 
 /**
- * 所有権譲渡キャンセル用のアカウント構造体
+ * 所有権譲渡承認イベント
  * 
- * 所有権譲渡をキャンセルする際に必要なアカウント情報を定義します。
- * この構造体はcancel_ownership_transfer命令の実行時に使用されます。
+ * 譲渡先が所有権譲渡を承認したときに発行されます。
+ * 多重署名の場合、所有権は保留中トランザクションの実行時に移転します。
  */
-#[derive(Accounts)]
-pub struct CancelOwnershipTransfer<'info> {
-    #[account(
-        mut,
-        seeds = [b"vault", owner.key().as_ref()],
-        bump = vault.bump,
-    )]
-    pub vault: Account<'info, Vault>,
-    
-    #[account(mut)]
-    pub owner: Signer<'info>,
+#[event]
+pub struct OwnershipTransferAccepted {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub new_owner: Pubkey,              // 譲渡先のアドレス
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
 }
 
 /**
- * トランザクション承認用のアカウント構造体
- * 
- * 保留中のトランザクションを承認する際に必要なアカウント情報を定義します。
- * この構造体はapprove_transaction命令の実行時に使用されます。
+ * 所有権譲渡キャンセルイベント
  */
-#[derive(Accounts)]
-pub struct ApproveTransaction<'info> {
-    #[account(
-        mut,
-        seeds = [b"vault", vault.owner.as_ref()],
-        bump = vault.bump,
-    )]
-    pub vault: Account<'info, Vault>,
-    
-    #[account(
-        mut,
-        constraint = vault_token_account.key() == vault.token_account,
-    )]
-    pub vault_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub destination_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub signer: Signer<'info>,
-    
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct OwnershipTransferCancelled {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub owner: Pubkey,                  // キャンセルした所有者
+    pub new_owner: Pubkey,              // 取り消された譲渡先のアドレス
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
 }
 
 /**
- * 金庫アカウントのデータ構造体
+ * 所有権移転イベント
  * 
- * 金庫の全ての状態を保存するためのメインデータ構造体です。
- * 所有者情報、トークンアカウント、セキュリティ設定、トランザクション情報などを含みます。
+ * 所有権譲渡、ソーシャルリカバリー、相続のいずれかで所有者が変わったときに発行されます。
  */
-#[account]
-pub struct Vault {
-    pub owner: Pubkey,                         // 金庫の所有者
-    pub token_account: Pubkey,                 // 金庫のトークンアカウント
-    pub bump: u8,                              // PDAのバンプシード
-    pub lock_until: u64,                       // タイムロック期限のUNIXタイムスタンプ
-    pub delegates: Vec<Pubkey>,                // 委任されたアドレスのリスト
-    pub multisig_threshold: u8,                // 必要な署名者数
-    pub multisig_signers: Vec<Pubkey>,         // 追加の署名者リスト（所有者は含まない）
-    pub pending_transactions: Vec<PendingTransaction>, // 保留中のトランザクション
-    pub max_withdrawal_limit: u64,             // 最大引き出し可能金額
-    pub transfer_ownership_to: Option<Pubkey>, // 所有権譲渡先
+#[event]
+pub struct OwnershipTransferred {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub previous_owner: Pubkey,         // 以前の所有者
+    pub new_owner: Pubkey,              // 新しい所有者
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
 }
 
 /**
- * 保留中トランザクションのデータ構造体
+ * 権利確定スケジュール作成イベント
+ */
+#[event]
+pub struct VestingScheduleCreated {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub schedule_id: u64,               // スケジュールID
+    pub beneficiary: Pubkey,            // 受益者のアドレス
+    pub start: u64,                     // 権利確定の開始UNIXタイムスタンプ
+    pub cliff: u64,                     // クリフのUNIXタイムスタンプ
+    pub end: u64,                       // 全額が確定するUNIXタイムスタンプ
+    pub total_amount: u64,              // 権利確定するトークンの総額
+    pub revocable: bool,                // 取り消し可能かどうか
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * 権利確定スケジュール取り消しイベント
+ */
+#[event]
+pub struct VestingRevoked {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub schedule_id: u64,               // スケジュールID
+    pub actor: Pubkey,                  // 取り消したアドレス
+    pub unvested_amount: u64,           // 確保が解除された未確定の金額
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * 定期支払い作成イベント
+ */
+#[event]
+pub struct PaymentStreamCreated {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub stream_id: u64,                 // 定期支払いID
+    pub recipient: Pubkey,              // 受取人のトークンアカウント
+    pub amount_per_period: u64,         // 1期間あたりの支払額
+    pub period_length: u64,             // 期間の長さ（秒）
+    pub start: u64,                     // 支払いの開始UNIXタイムスタンプ
+    pub end: u64,                       // 支払いの終了UNIXタイムスタンプ（0は終了なし）
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * 定期支払い停止イベント
+ */
+#[event]
+pub struct PaymentStreamCancelled {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub stream_id: u64,                 // 定期支払いID
+    pub actor: Pubkey,                  // 停止したアドレス
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * 一時停止イベント
  * 
- * 多重署名が必要なトランザクションの情報を保存します。
- * トランザクションの種類、金額、送信先、署名者リストなどを含みます。
+ * 一時停止の解除は設定変更としてConfigChangedイベントで通知されます。
  */
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct PendingTransaction {
-    pub id: u64,                        // トランザクションID
-    pub transaction_type: TransactionType, // トランザクションの種類
-    pub amount: u64,                    // 引き出し量（引き出しの場合）
-    pub destination: Pubkey,            // 送金先（引き出しの場合）
-    pub new_owner: Option<Pubkey>,      // 所有権譲渡先（TransferOwnershipの場合のみ使用）
-    pub signers: Vec<Pubkey>,           // 署名者リスト
-    pub executed: bool,                 // 実行済みフラグ
-    pub created_at: u64,                // 作成時刻
+#[event]
+pub struct Paused {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub actor: Pubkey,                  // 一時停止した所有者またはガーディアン
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
 }
 
 /**
- * トランザクション種類の列挙型
+ * リカバリー提案イベント
+ */
+#[event]
+pub struct RecoveryProposed {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub guardian: Pubkey,               // 提案したガーディアン
+    pub new_owner: Pubkey,              // 提案された新しい所有者
+    pub executable_at: u64,             // 確定できるようになるUNIXタイムスタンプ（承認が足りない場合は0）
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * リカバリー承認イベント
+ */
+#[event]
+pub struct RecoveryApproved {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub guardian: Pubkey,               // 承認したガーディアン
+    pub approvals: u8,                  // 現在の承認数
+    pub executable_at: u64,             // 確定できるようになるUNIXタイムスタンプ（承認が足りない場合は0）
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * リカバリー拒否イベント
+ */
+#[event]
+pub struct RecoveryVetoed {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub owner: Pubkey,                  // 拒否した所有者
+    pub new_owner: Pubkey,              // 取り消された新しい所有者
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
+}
+
+/**
+ * リカバリー確定イベント
  * 
- * 保留中トランザクションの種類を定義します。
+ * 所有者の変更はOwnershipTransferredイベントでも通知されます。
  */
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Copy)]
-pub enum TransactionType {
-    Withdraw,           // トークン引き出し
-    TransferOwnership,  // 所有権譲渡
+#[event]
+pub struct RecoveryFinalized {
+    pub vault: Pubkey,                  // 金庫のアドレス
+    pub previous_owner: Pubkey,         // 以前の所有者
+    pub new_owner: Pubkey,              // 新しい所有者
+    pub approvals: u8,                  // 確定時のガーディアンの承認数
+    pub timestamp: u64,                 // 発生時刻のUNIXタイムスタンプ
 }
 
 /**
@@ -870,4 +6178,108 @@ pub enum VaultError {
     ExceedsWithdrawalLimit,         // 引き出し制限超過エラー
     #[msg("No ownership transfer is pending")]
     NoOwnershipTransferPending,     // 所有権譲渡未保留エラー
+    #[msg("Pending transactions must be resolved first")]
+    PendingTransactionsExist,       // 保留中トランザクション存在エラー
+    #[msg("Not enough approvals to execute this transaction")]
+    InsufficientApprovals,          // 承認数不足エラー
+    #[msg("Maximum number of delegates reached, resize the vault first")]
+    TooManyDelegates,               // 委任者数上限エラー
+    #[msg("Maximum number of multisig signers reached, resize the vault first")]
+    TooManySigners,                 // 署名者数上限エラー
+    #[msg("Requested capacity cannot hold the current vault data")]
+    InvalidCapacity,                // 無効な容量エラー
+    #[msg("Pending transaction account is missing or not expected in this mode")]
+    InvalidPendingTransactionAccount, // 保留中トランザクションアカウント不正エラー
+    #[msg("Transaction is still waiting for approval")]
+    TransactionStillPending,        // 承認待ちトランザクションエラー
+    #[msg("Transaction has expired")]
+    ProposalExpired,                // トランザクション期限切れエラー
+    #[msg("Transaction has not expired and is still valid")]
+    ProposalNotExpired,             // トランザクション期限内エラー
+    #[msg("Signer has not approved this transaction")]
+    ApprovalNotFound,               // 承認未検出エラー
+    #[msg("This transaction type cannot be handled by this instruction")]
+    InvalidTransactionType,         // 無効なトランザクション種類エラー
+    #[msg("Transaction was created under a previous multisig configuration")]
+    StaleTransaction,               // 旧設定トランザクションエラー
+    #[msg("Destination does not match the approved transaction")]
+    DestinationMismatch,            // 送金先不一致エラー
+    #[msg("Token account mint does not match the vault")]
+    InvalidMint,                    // ミント不一致エラー
+    #[msg("Withdrawal amount exceeds the remaining budget for the current window")]
+    ExceedsWindowBudget,            // 期間ごとの引き出し枠超過エラー
+    #[msg("Delegation has expired")]
+    DelegateExpired,                // 委任期限切れエラー
+    #[msg("Delegate is not permitted to perform this action")]
+    DelegatePermissionDenied,       // 委任権限不足エラー
+    #[msg("Withdrawal amount exceeds the delegate's allowance")]
+    ExceedsDelegateAllowance,       // 委任者の引き出し枠超過エラー
+    #[msg("Invalid delegate permissions")]
+    InvalidDelegatePermissions,     // 無効な委任権限エラー
+    #[msg("Maximum number of allowlist entries reached, resize the vault first")]
+    TooManyAllowlistEntries,        // 許可リスト登録数上限エラー
+    #[msg("Destination is not on the allowlist or not active yet")]
+    DestinationNotAllowed,          // 許可されていない送金先エラー
+    #[msg("Withdrawals must be requested and wait for the withdrawal delay")]
+    WithdrawalDelayRequired,        // 引き出し予約必須エラー
+    #[msg("Withdrawal request is still in its delay period")]
+    WithdrawalNotReady,             // 引き出し予約待機中エラー
+    #[msg("Withdrawals from this vault require multisig approval")]
+    MultisigApprovalRequired,       // 多重署名承認必須エラー
+    #[msg("Approval tiers must be sorted by amount and within the maximum count")]
+    InvalidApprovalTiers,           // 無効な承認段階エラー
+    #[msg("Timelock timestamp overflowed")]
+    TimelockOverflow,               // タイムロック期限オーバーフローエラー
+    #[msg("Timelock can only be extended in extend-only mode")]
+    LockCannotBeShortened,          // タイムロック短縮不可エラー
+    #[msg("Extend-only lock mode cannot be turned off")]
+    LockModeIrreversible,           // ロックモード変更不可エラー
+    #[msg("Withdrawal would use tokens reserved for vesting")]
+    InsufficientUnreservedBalance,  // 確保済み残高エラー
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,         // 無効な権利確定スケジュールエラー
+    #[msg("No vested tokens to release")]
+    NothingToRelease,               // 支払い可能額なしエラー
+    #[msg("Vesting schedule is not revocable")]
+    VestingNotRevocable,            // 権利確定取り消し不可エラー
+    #[msg("Vesting schedule has not been fully released")]
+    VestingNotComplete,             // 権利確定未完了エラー
+    #[msg("Vested tokens must be released or revoked first")]
+    VestingSchedulesExist,          // 権利確定スケジュール存在エラー
+    #[msg("Invalid payment stream")]
+    InvalidPaymentStream,           // 無効な定期支払いエラー
+    #[msg("No payment has accrued since the last claim")]
+    NothingAccrued,                 // 支払い発生なしエラー
+    #[msg("Vault is paused")]
+    VaultPaused,                    // 一時停止中エラー
+    #[msg("Maximum number of guardians exceeded")]
+    TooManyGuardians,               // ガーディアン数上限エラー
+    #[msg("Guardian recovery is not configured")]
+    RecoveryNotConfigured,          // リカバリー未設定エラー
+    #[msg("A recovery is already in progress")]
+    RecoveryInProgress,             // リカバリー進行中エラー
+    #[msg("No recovery is pending")]
+    NoRecoveryPending,              // リカバリー未保留エラー
+    #[msg("Recovery does not have enough approvals or is still in its delay period")]
+    RecoveryNotReady,               // リカバリー待機中エラー
+    #[msg("Owner has been active within the inactivity period")]
+    OwnerStillActive,               // 所有者活動中エラー
+    #[msg("This vault keeps an audit log, which must be provided")]
+    AuditLogRequired,               // 監査ログ未指定エラー
+    #[msg("Mint is not registered with this vault")]
+    MintNotRegistered,              // 未登録ミントエラー
+    #[msg("Mint is already registered with this vault")]
+    MintAlreadyRegistered,          // 登録済みミントエラー
+    #[msg("Too many mints registered")]
+    TooManyMints,                   // ミント登録数超過エラー
+    #[msg("Token account of the mint still holds tokens")]
+    MintAccountNotEmpty,            // ミント残高ありエラー
+    #[msg("Registered mints must be unregistered before closing the vault")]
+    MintsRegistered,                // 登録済みミントありエラー
+    #[msg("Mint uses a token extension that is not supported")]
+    UnsupportedMintExtension,       // 非対応の拡張機能エラー
+    #[msg("Withdrawal requests must be executed or cancelled before closing the vault")]
+    WithdrawalRequestsExist,        // 引き出し予約ありエラー
+    #[msg("Payment streams must be cancelled before closing the vault")]
+    PaymentStreamsExist,            // 定期支払いありエラー
 }
@@ -1,6 +1,18 @@
 const anchor = require("@coral-xyz/anchor");
 const { SystemProgram, PublicKey, Keypair } = require("@solana/web3.js");
-const { TOKEN_PROGRAM_ID, MINT_SIZE, createMint, createAccount, mintTo } = require("@solana/spl-token");
+const {
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  MINT_SIZE,
+  ExtensionType,
+  createMint,
+  createAccount,
+  mintTo,
+  getMintLen,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createInitializePermanentDelegateInstruction,
+} = require("@solana/spl-token");
 const assert = require("assert");
 
 describe("simple_vault", () => {
@@ -22,6 +34,8 @@ describe("simple_vault", () => {
   let vaultTokenAccount;
   let vaultPDA;
   let vaultBump;
+  const vaultId = new anchor.BN(0); // 最初の金庫ID
+  const secondVaultId = new anchor.BN(1); // 同じ所有者の2つ目の金庫ID
 
   const depositAmount = new anchor.BN(1000000);
   const withdrawAmount = new anchor.BN(200000);
@@ -29,6 +43,95 @@ describe("simple_vault", () => {
   const multisigWithdrawAmount = new anchor.BN(300000);
   const withdrawalLimit = new anchor.BN(250000); // 最大引き出し額の制限
   const exceedingAmount = new anchor.BN(300000); // 制限を超える額
+  const DELEGATE_PERMISSION_WITHDRAW = 1; // 委任者の引き出し権限フラグ
+  const allowlistDelay = new anchor.BN(5); // 許可リストの待機時間（秒）
+
+  // 保留中トランザクションのPDAを導出する（金庫の指定がなければ最初の金庫）
+  const transactionPDA = (txId, vault = vaultPDA) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("transaction"), vault.toBuffer(), new anchor.BN(txId).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+
+  // 権利確定スケジュールのPDAを導出する
+  const vestingSchedulePDA = (scheduleId) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vesting"), vaultPDA.toBuffer(), new anchor.BN(scheduleId).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+
+  // 定期支払いのPDAを導出する
+  const paymentStreamPDA = (streamId) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("stream"), vaultPDA.toBuffer(), new anchor.BN(streamId).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+
+  // 指定した拡張機能を持つToken-2022のミントを作成する
+  const createToken2022Mint = async (extensions, extensionInstructions) => {
+    const mint = anchor.web3.Keypair.generate();
+    const mintLen = getMintLen(extensions);
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(mintLen);
+    const tx = new anchor.web3.Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: provider.wallet.publicKey,
+        newAccountPubkey: mint.publicKey,
+        space: mintLen,
+        lamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      ...extensionInstructions(mint.publicKey),
+      createInitializeMintInstruction(mint.publicKey, 6, provider.wallet.publicKey, null, TOKEN_2022_PROGRAM_ID)
+    );
+    await provider.sendAndConfirm(tx, [mint]);
+    return mint.publicKey;
+  };
+
+  // 引き出し予約のPDAを導出する
+  const withdrawalRequestPDA = (requestId) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("withdrawal"), vaultPDA.toBuffer(), new anchor.BN(requestId).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+
+  // 所有者の新しい金庫を作成し、金庫とトークンアカウントのアドレスを返す
+  const initializeVault = async (id) => {
+    const [vault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), ownerKeypair.publicKey.toBuffer(), new anchor.BN(id).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const tokenAccount = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .initialize(new anchor.BN(id))
+      .accounts({
+        vault,
+        vaultTokenAccount: tokenAccount.publicKey,
+        mint: mintKeypair.publicKey,
+        owner: ownerKeypair.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([ownerKeypair, tokenAccount])
+      .rpc();
+
+    return { vault, tokenAccount: tokenAccount.publicKey };
+  };
+
+  // 金庫に2-of-3の多重署名を設定する
+  const enableMultisig = async (vault) => {
+    await program.methods
+      .setMultisig(2, [multisigSigner1.publicKey, multisigSigner2.publicKey])
+      .accounts({
+        vault,
+        pendingTransaction: null,
+        owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+  };
 
   before(async () => {
     // Airdrop SOL to owner, delegate, multisig signers, and new owner
@@ -89,7 +192,7 @@ describe("simple_vault", () => {
 
     // Derive vault PDA
     [vaultPDA, vaultBump] = await PublicKey.findProgramAddress(
-      [Buffer.from("vault"), ownerKeypair.publicKey.toBuffer()],
+      [Buffer.from("vault"), ownerKeypair.publicKey.toBuffer(), vaultId.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
 
@@ -101,7 +204,7 @@ describe("simple_vault", () => {
 
   it("Initializes the vault", async () => {
     await program.methods
-      .initialize()
+      .initialize(vaultId)
       .accounts({
         vault: vaultPDA,
         vaultTokenAccount: vaultTokenAccount.publicKey,
@@ -117,25 +220,86 @@ describe("simple_vault", () => {
     // Verify vault data
     const vaultAccount = await program.account.vault.fetch(vaultPDA);
     assert.equal(vaultAccount.owner.toString(), ownerKeypair.publicKey.toString());
+    assert.equal(vaultAccount.creator.toString(), ownerKeypair.publicKey.toString());
+    assert.equal(vaultAccount.vaultId.toNumber(), vaultId.toNumber());
     assert.equal(vaultAccount.tokenAccount.toString(), vaultTokenAccount.publicKey.toString());
     assert.equal(vaultAccount.bump, vaultBump);
     assert.equal(vaultAccount.lockUntil.toNumber(), 0); // 初期状態ではロックなし
     assert.equal(vaultAccount.delegates.length, 0); // 初期状態では委任なし
     assert.equal(vaultAccount.multisigThreshold, 1); // 初期状態では単一署名
     assert.equal(vaultAccount.multisigSigners.length, 0); // 初期状態では追加の署名者なし
-    assert.equal(vaultAccount.pendingTransactions.length, 0); // 初期状態では保留中のトランザクションなし
+    assert.equal(vaultAccount.transactionCount.toNumber(), 0); // 初期状態では保留中のトランザクションなし
+    assert.equal(vaultAccount.openTransactions, 0); // 初期状態では承認待ちのトランザクションなし
     assert.equal(vaultAccount.maxWithdrawalLimit.toString(), new anchor.BN(2).pow(new anchor.BN(64)).sub(new anchor.BN(1)).toString()); // 初期状態では制限なし
     assert.equal(vaultAccount.transferOwnershipTo, null); // 初期状態では所有権譲渡先なし
+    assert.equal(vaultAccount.maxDelegates, 10); // 初期状態の委任者上限
+    assert.equal(vaultAccount.maxSigners, 5); // 初期状態の署名者上限
+    assert.equal(vaultAccount.maxAllowlist, 10); // 初期状態の許可リスト上限
+    assert.equal(vaultAccount.transactionTtl.toNumber(), 7 * 24 * 60 * 60); // 保留中トランザクションの有効期間は7日間
+    assert.equal(vaultAccount.configVersion, 0); // 初期状態の署名者設定バージョン
+  });
+
+  it("Resizes the vault to hold more signers", async () => {
+    const sizeBefore = (await provider.connection.getAccountInfo(vaultPDA)).data.length;
+
+    await program.methods
+      .resizeVault(10, 20, 10)
+      .accounts({
+        vault: vaultPDA,
+        owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    // Verify the account grew and the new capacity is recorded
+    const sizeAfter = (await provider.connection.getAccountInfo(vaultPDA)).data.length;
+    const vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert(sizeAfter > sizeBefore, "Vault account should grow");
+    assert.equal(vaultAccount.maxSigners, 20, "Signer capacity should be updated");
+  });
+
+  it("Initializes a second vault under the same owner", async () => {
+    const [secondVaultPDA] = await PublicKey.findProgramAddress(
+      [Buffer.from("vault"), ownerKeypair.publicKey.toBuffer(), secondVaultId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const secondVaultTokenAccount = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .initialize(secondVaultId)
+      .accounts({
+        vault: secondVaultPDA,
+        vaultTokenAccount: secondVaultTokenAccount.publicKey,
+        mint: mintKeypair.publicKey,
+        owner: ownerKeypair.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([ownerKeypair, secondVaultTokenAccount])
+      .rpc();
+
+    // Verify both vaults exist independently
+    const secondVaultAccount = await program.account.vault.fetch(secondVaultPDA);
+    assert.equal(secondVaultAccount.owner.toString(), ownerKeypair.publicKey.toString());
+    assert.equal(secondVaultAccount.vaultId.toNumber(), secondVaultId.toNumber());
+    assert.notEqual(secondVaultPDA.toString(), vaultPDA.toString(), "Vault addresses should differ");
   });
 
   it("Deposits tokens to the vault", async () => {
     const balanceBefore = await provider.connection.getTokenAccountBalance(userTokenAccount);
+    let depositedEvent = null;
+    const listener = program.addEventListener("Deposited", (event) => {
+      depositedEvent = event;
+    });
     
     await program.methods
-      .deposit(depositAmount)
+      .deposit(depositAmount, mintKeypair.publicKey)
       .accounts({
         vault: vaultPDA,
         vaultTokenAccount: vaultTokenAccount.publicKey,
+        tokenMint: mintKeypair.publicKey,
         userTokenAccount: userTokenAccount,
         owner: ownerKeypair.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -152,6 +316,14 @@ describe("simple_vault", () => {
       depositAmount.toNumber()
     );
     assert.equal(Number(vaultBalance.value.amount), depositAmount.toNumber());
+
+    // Indexers learn about the deposit from the emitted event
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+    await program.removeEventListener(listener);
+    assert(depositedEvent !== null, "Deposited event should be emitted");
+    assert.equal(depositedEvent.vault.toString(), vaultPDA.toString());
+    assert.equal(depositedEvent.depositor.toString(), ownerKeypair.publicKey.toString());
+    assert.equal(depositedEvent.amount.toNumber(), depositAmount.toNumber());
   });
 
   it("Queries the vault balance", async () => {
@@ -171,7 +343,9 @@ describe("simple_vault", () => {
       .setTimelock(lockDuration)
       .accounts({
         vault: vaultPDA,
+        pendingTransaction: null,
         owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
       })
       .signers([ownerKeypair])
       .rpc();
@@ -181,16 +355,62 @@ describe("simple_vault", () => {
     assert(vaultAccount.lockUntil.toNumber() > 0, "Timelock should be set");
   });
 
+  it("Cannot shorten a timelock in extend-only mode", async () => {
+    // Use the second vault so the lock does not affect the other tests
+    const [secondVaultPDA] = await PublicKey.findProgramAddress(
+      [Buffer.from("vault"), ownerKeypair.publicKey.toBuffer(), secondVaultId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const lockAccounts = {
+      vault: secondVaultPDA,
+      pendingTransaction: null,
+      owner: ownerKeypair.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+    const unlockAt = Math.floor(Date.now() / 1000) + 3600;
+
+    await program.methods
+      .setLockMode({ extendOnly: {} })
+      .accounts(lockAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    await program.methods
+      .setTimelockUntil(new anchor.BN(unlockAt))
+      .accounts(lockAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    const secondVaultAccount = await program.account.vault.fetch(secondVaultPDA);
+    assert.equal(secondVaultAccount.lockUntil.toNumber(), unlockAt, "Lock should end at the given timestamp");
+
+    // A shorter lock is refused
+    try {
+      await program.methods
+        .setTimelock(new anchor.BN(1))
+        .accounts(lockAccounts)
+        .signers([ownerKeypair])
+        .rpc();
+
+      assert.fail("Should have thrown an error due to shortening the lock");
+    } catch (error) {
+      assert(error.toString().includes("LockCannotBeShortened"), "Expected LockCannotBeShortened error");
+    }
+  });
+
   it("Cannot withdraw when vault is locked", async () => {
     try {
       await program.methods
-        .withdraw(withdrawAmount)
+        .withdraw(withdrawAmount, mintKeypair.publicKey)
         .accounts({
           vault: vaultPDA,
           vaultTokenAccount: vaultTokenAccount.publicKey,
+          tokenMint: mintKeypair.publicKey,
           userTokenAccount: userTokenAccount,
+          pendingTransaction: null,
           owner: ownerKeypair.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
         .signers([ownerKeypair])
         .rpc();
@@ -210,13 +430,16 @@ describe("simple_vault", () => {
     const vaultBalanceBefore = await provider.connection.getTokenAccountBalance(vaultTokenAccount.publicKey);
     
     await program.methods
-      .withdraw(withdrawAmount)
+      .withdraw(withdrawAmount, mintKeypair.publicKey)
       .accounts({
         vault: vaultPDA,
         vaultTokenAccount: vaultTokenAccount.publicKey,
+        tokenMint: mintKeypair.publicKey,
         userTokenAccount: userTokenAccount,
+        pendingTransaction: null,
         owner: ownerKeypair.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
       })
       .signers([ownerKeypair])
       .rpc();
@@ -237,10 +460,18 @@ describe("simple_vault", () => {
 
   it("Adds a delegate to the vault", async () => {
     await program.methods
-      .addDelegate(delegateKeypair.publicKey)
+      .addDelegate(
+        delegateKeypair.publicKey,
+        withdrawAmount, // 引き出し枠
+        new anchor.BN(0), // 補充なし
+        new anchor.BN(0), // 期限なし
+        DELEGATE_PERMISSION_WITHDRAW
+      )
       .accounts({
         vault: vaultPDA,
+        pendingTransaction: null,
         owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
       })
       .signers([ownerKeypair])
       .rpc();
@@ -249,10 +480,11 @@ describe("simple_vault", () => {
     const vaultAccount = await program.account.vault.fetch(vaultPDA);
     assert.equal(vaultAccount.delegates.length, 1, "Should have one delegate");
     assert.equal(
-      vaultAccount.delegates[0].toString(),
+      vaultAccount.delegates[0].delegate.toString(),
       delegateKeypair.publicKey.toString(),
       "Delegate should match"
     );
+    assert.equal(vaultAccount.delegates[0].allowance.toNumber(), withdrawAmount.toNumber(), "Allowance should match");
   });
 
   it("Delegate can withdraw from the vault", async () => {
@@ -261,13 +493,16 @@ describe("simple_vault", () => {
     
     // Delegate withdraws tokens
     await program.methods
-      .withdraw(withdrawAmount)
+      .withdraw(withdrawAmount, mintKeypair.publicKey)
       .accounts({
         vault: vaultPDA,
         vaultTokenAccount: vaultTokenAccount.publicKey,
+        tokenMint: mintKeypair.publicKey,
         userTokenAccount: delegateTokenAccount,
+        pendingTransaction: null,
         owner: delegateKeypair.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
       })
       .signers([delegateKeypair])
       .rpc();
@@ -286,12 +521,58 @@ describe("simple_vault", () => {
     );
   });
 
+  it("Delegate cannot withdraw beyond its allowance", async () => {
+    try {
+      await program.methods
+        .withdraw(new anchor.BN(1), mintKeypair.publicKey)
+        .accounts({
+          vault: vaultPDA,
+          vaultTokenAccount: vaultTokenAccount.publicKey,
+          tokenMint: mintKeypair.publicKey,
+          userTokenAccount: delegateTokenAccount,
+          pendingTransaction: null,
+          owner: delegateKeypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([delegateKeypair])
+        .rpc();
+
+      assert.fail("Should have thrown an error due to the exhausted allowance");
+    } catch (error) {
+      assert(error.toString().includes("ExceedsDelegateAllowance"), "Expected ExceedsDelegateAllowance error");
+    }
+  });
+
+  it("Delegate cannot deposit without the deposit permission", async () => {
+    try {
+      await program.methods
+        .deposit(new anchor.BN(1), mintKeypair.publicKey)
+        .accounts({
+          vault: vaultPDA,
+          vaultTokenAccount: vaultTokenAccount.publicKey,
+          tokenMint: mintKeypair.publicKey,
+          userTokenAccount: delegateTokenAccount,
+          owner: delegateKeypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([delegateKeypair])
+        .rpc();
+
+      assert.fail("Should have thrown an error due to the missing permission");
+    } catch (error) {
+      assert(error.toString().includes("DelegatePermissionDenied"), "Expected DelegatePermissionDenied error");
+    }
+  });
+
   it("Removes a delegate from the vault", async () => {
     await program.methods
       .removeDelegate(delegateKeypair.publicKey)
       .accounts({
         vault: vaultPDA,
+        pendingTransaction: null,
         owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
       })
       .signers([ownerKeypair])
       .rpc();
@@ -304,13 +585,16 @@ describe("simple_vault", () => {
   it("Former delegate cannot withdraw after removal", async () => {
     try {
       await program.methods
-        .withdraw(withdrawAmount)
+        .withdraw(withdrawAmount, mintKeypair.publicKey)
         .accounts({
           vault: vaultPDA,
           vaultTokenAccount: vaultTokenAccount.publicKey,
+          tokenMint: mintKeypair.publicKey,
           userTokenAccount: delegateTokenAccount,
+          pendingTransaction: null,
           owner: delegateKeypair.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
         .signers([delegateKeypair])
         .rpc();
@@ -328,7 +612,9 @@ describe("simple_vault", () => {
       .setMultisig(2, [multisigSigner1.publicKey, multisigSigner2.publicKey])
       .accounts({
         vault: vaultPDA,
+        pendingTransaction: null,
         owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
       })
       .signers([ownerKeypair])
       .rpc();
@@ -337,6 +623,7 @@ describe("simple_vault", () => {
     const vaultAccount = await program.account.vault.fetch(vaultPDA);
     assert.equal(vaultAccount.multisigThreshold, 2, "Threshold should be 2");
     assert.equal(vaultAccount.multisigSigners.length, 2, "Should have 2 multisig signers");
+    assert.equal(vaultAccount.configVersion, 1, "Config version should advance with the signer set");
     assert.equal(
       vaultAccount.multisigSigners[0].toString(),
       multisigSigner1.publicKey.toString(),
@@ -352,25 +639,35 @@ describe("simple_vault", () => {
   it("Creates a pending transaction when withdraw is initiated in multisig mode", async () => {
     // Try to withdraw - this should create a pending transaction
     await program.methods
-      .withdraw(multisigWithdrawAmount)
+      .withdraw(multisigWithdrawAmount, mintKeypair.publicKey)
       .accounts({
         vault: vaultPDA,
         vaultTokenAccount: vaultTokenAccount.publicKey,
+        tokenMint: mintKeypair.publicKey,
         userTokenAccount: userTokenAccount,
+        pendingTransaction: transactionPDA(0),
         owner: ownerKeypair.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
       })
       .signers([ownerKeypair])
       .rpc();
     
     // Verify pending transaction was created
     const vaultAccount = await program.account.vault.fetch(vaultPDA);
-    assert.equal(vaultAccount.pendingTransactions.length, 1, "Should have one pending transaction");
-    assert.equal(vaultAccount.pendingTransactions[0].executed, false, "Transaction should not be executed yet");
-    assert.equal(vaultAccount.pendingTransactions[0].amount.toNumber(), multisigWithdrawAmount.toNumber(), "Amount should match");
-    assert.equal(vaultAccount.pendingTransactions[0].signers.length, 1, "Should have owner's signature");
+    const pendingTx = await program.account.pendingTransaction.fetch(transactionPDA(0));
+    assert.equal(vaultAccount.transactionCount.toNumber(), 1, "Should have one pending transaction");
+    assert.equal(vaultAccount.openTransactions, 1, "Transaction should be waiting for approval");
+    assert(pendingTx.status.pending !== undefined, "Transaction should not be executed yet");
+    assert.equal(pendingTx.amount.toNumber(), multisigWithdrawAmount.toNumber(), "Amount should match");
+    assert.equal(
+      pendingTx.expiresAt.toNumber(),
+      pendingTx.createdAt.toNumber() + vaultAccount.transactionTtl.toNumber(),
+      "Expiry should be stamped from the vault's TTL"
+    );
+    assert.equal(pendingTx.signers.length, 1, "Should have owner's signature");
     assert.equal(
-      vaultAccount.pendingTransactions[0].signers[0].toString(),
+      pendingTx.signers[0].toString(),
       ownerKeypair.publicKey.toString(),
       "First signature should be from owner"
     );
@@ -386,17 +683,49 @@ describe("simple_vault", () => {
       .approveTransaction(new anchor.BN(txId))
       .accounts({
         vault: vaultPDA,
+        pendingTransaction: transactionPDA(txId),
+        signer: multisigSigner1.publicKey,
+      })
+      .signers([multisigSigner1])
+      .rpc();
+
+    // The last signer cannot redirect the withdrawal to another account
+    try {
+      await program.methods
+        .executeTransaction(new anchor.BN(txId))
+        .accounts({
+          vault: vaultPDA,
+          pendingTransaction: transactionPDA(txId),
+          vaultTokenAccount: vaultTokenAccount.publicKey,
+          tokenMint: mintKeypair.publicKey,
+          destinationTokenAccount: multisigSigner1TokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      assert.fail("Should have thrown an error due to destination mismatch");
+    } catch (error) {
+      assert(error.toString().includes("DestinationMismatch"), "Expected DestinationMismatch error");
+    }
+
+    // Anyone can execute once the threshold is met
+    await program.methods
+      .executeTransaction(new anchor.BN(txId))
+      .accounts({
+        vault: vaultPDA,
+        pendingTransaction: transactionPDA(txId),
         vaultTokenAccount: vaultTokenAccount.publicKey,
+        tokenMint: mintKeypair.publicKey,
         destinationTokenAccount: userTokenAccount,
-        signer: multisigSigner1.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
-      .signers([multisigSigner1])
       .rpc();
     
     // Verify transaction executed
     const vaultAccount = await program.account.vault.fetch(vaultPDA);
-    assert.equal(vaultAccount.pendingTransactions[0].executed, true, "Transaction should be executed");
+    const pendingTx = await program.account.pendingTransaction.fetch(transactionPDA(txId));
+    assert(pendingTx.status.executed !== undefined, "Transaction should be executed");
+    assert.equal(vaultAccount.openTransactions, 0, "No transaction should be waiting for approval");
     
     // Verify token balances
     const userBalance = await provider.connection.getTokenAccountBalance(userTokenAccount);
@@ -414,141 +743,1722 @@ describe("simple_vault", () => {
     );
   });
 
-  it("Sets a withdrawal limit", async () => {
-    // Set a withdrawal limit
+  it("Closes the executed transaction and refunds rent to the proposer", async () => {
+    const proposerBalanceBefore = await provider.connection.getBalance(ownerKeypair.publicKey);
+
     await program.methods
-      .setWithdrawalLimit(withdrawalLimit)
+      .closeTransaction()
       .accounts({
-        vault: vaultPDA,
-        owner: ownerKeypair.publicKey,
+        pendingTransaction: transactionPDA(0),
+        proposer: ownerKeypair.publicKey,
       })
-      .signers([ownerKeypair])
       .rpc();
-    
-    // Verify withdrawal limit was set
-    const vaultAccount = await program.account.vault.fetch(vaultPDA);
-    assert.equal(vaultAccount.maxWithdrawalLimit.toNumber(), withdrawalLimit.toNumber(), "Withdrawal limit should be set");
-  });
 
-  it("Cannot withdraw more than the limit", async () => {
-    try {
-      await program.methods
-        .withdraw(exceedingAmount)
-        .accounts({
-          vault: vaultPDA,
-          vaultTokenAccount: vaultTokenAccount.publicKey,
-          userTokenAccount: userTokenAccount,
-          owner: ownerKeypair.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
-        })
-        .signers([ownerKeypair])
-        .rpc();
-      
-      assert.fail("Should have thrown an error due to exceeding withdrawal limit");
-    } catch (error) {
-      assert(error.toString().includes("ExceedsWithdrawalLimit"), "Expected ExceedsWithdrawalLimit error");
-    }
+    // Verify the account was closed and the rent returned
+    const proposerBalance = await provider.connection.getBalance(ownerKeypair.publicKey);
+    assert.equal(await provider.connection.getAccountInfo(transactionPDA(0)), null, "Transaction account should be closed");
+    assert(proposerBalance > proposerBalanceBefore, "Proposer should receive the rent");
   });
 
-  it("Can withdraw up to the limit", async () => {
-    const userBalanceBefore = await provider.connection.getTokenAccountBalance(userTokenAccount);
-    const vaultBalanceBefore = await provider.connection.getTokenAccountBalance(vaultTokenAccount.publicKey);
-    
+  it("Rejects a withdrawal once the threshold can no longer be reached", async () => {
+    const txId = 1;
+
+    // Owner proposes another withdrawal
     await program.methods
-      .withdraw(withdrawalLimit)
+      .withdraw(multisigWithdrawAmount, mintKeypair.publicKey)
       .accounts({
         vault: vaultPDA,
         vaultTokenAccount: vaultTokenAccount.publicKey,
+        tokenMint: mintKeypair.publicKey,
         userTokenAccount: userTokenAccount,
+        pendingTransaction: transactionPDA(txId),
         owner: ownerKeypair.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
       })
       .signers([ownerKeypair])
       .rpc();
 
-    // Verify token balances
-    const userBalance = await provider.connection.getTokenAccountBalance(userTokenAccount);
-    const vaultBalance = await provider.connection.getTokenAccountBalance(vaultTokenAccount.publicKey);
-    
-    assert.equal(
-      Number(userBalance.value.amount) - Number(userBalanceBefore.value.amount),
-      withdrawalLimit.toNumber(),
-      "User balance should increase by withdraw amount"
-    );
-    assert.equal(
-      Number(vaultBalanceBefore.value.amount) - Number(vaultBalance.value.amount),
-      withdrawalLimit.toNumber(),
-      "Vault balance should decrease by withdraw amount"
-    );
+    // Both additional signers vote no (3 eligible, threshold 2)
+    for (const signer of [multisigSigner1, multisigSigner2]) {
+      await program.methods
+        .rejectTransaction(new anchor.BN(txId))
+        .accounts({
+          vault: vaultPDA,
+          pendingTransaction: transactionPDA(txId),
+          signer: signer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+    }
+
+    // Verify the transaction is permanently rejected
+    const vaultAccount = await program.account.vault.fetch(vaultPDA);
+    const pendingTx = await program.account.pendingTransaction.fetch(transactionPDA(txId));
+    assert(pendingTx.status.rejected !== undefined, "Transaction should be rejected");
+    assert.equal(pendingTx.rejections.length, 2, "Should record both rejections");
+    assert.equal(vaultAccount.openTransactions, 0, "No transaction should be waiting for approval");
   });
 
-  it("Initiates ownership transfer", async () => {
-    // Reset multisig to single signature for simplicity
+  it("Expired proposals cannot be approved or executed and anyone can clean them up", async () => {
+    const { vault, tokenAccount } = await initializeVault(6);
+
+    // Proposals on this vault expire two seconds after they are made
     await program.methods
-      .setMultisig(1, [])
+      .setTransactionTtl(new anchor.BN(2))
       .accounts({
-        vault: vaultPDA,
+        vault,
+        pendingTransaction: null,
         owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
       })
       .signers([ownerKeypair])
       .rpc();
+    await enableMultisig(vault);
 
-    // Initiate ownership transfer
     await program.methods
-      .initiateOwnershipTransfer(newOwnerKeypair.publicKey)
+      .setWithdrawalLimit(new anchor.BN(1000))
       .accounts({
-        vault: vaultPDA,
+        vault,
+        pendingTransaction: transactionPDA(0, vault),
         owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
       })
       .signers([ownerKeypair])
       .rpc();
-    
-    // Verify pending ownership transfer
-    const vaultAccount = await program.account.vault.fetch(vaultPDA);
-    assert(vaultAccount.transferOwnershipTo !== null, "Should have pending ownership transfer");
-    assert.equal(
-      vaultAccount.transferOwnershipTo.toString(),
-      newOwnerKeypair.publicKey.toString(),
-      "Pending owner should match"
-    );
-  });
 
-  it("Cannot accept ownership if not the intended new owner", async () => {
+    // Wait for the proposal to expire
+    await new Promise((resolve) => setTimeout(resolve, 4000));
+
     try {
       await program.methods
-        .acceptOwnership()
+        .approveTransaction(new anchor.BN(0))
         .accounts({
-          vault: vaultPDA,
-          newOwner: delegateKeypair.publicKey,
+          vault,
+          pendingTransaction: transactionPDA(0, vault),
+          signer: multisigSigner1.publicKey,
         })
-        .signers([delegateKeypair])
+        .signers([multisigSigner1])
         .rpc();
-      
-      assert.fail("Should have thrown an error due to unauthorized access");
+
+      assert.fail("Should have thrown an error due to the expired proposal");
     } catch (error) {
-      assert(error.toString().includes("Unauthorized"), "Expected Unauthorized error");
+      assert(error.toString().includes("ProposalExpired"), "Expected ProposalExpired error");
     }
-  });
 
-  it("New owner can accept ownership", async () => {
+    try {
+      await program.methods
+        .executeTransaction(new anchor.BN(0))
+        .accounts({
+          vault,
+          pendingTransaction: transactionPDA(0, vault),
+          vaultTokenAccount: tokenAccount,
+          tokenMint: mintKeypair.publicKey,
+          destinationTokenAccount: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      assert.fail("Should have thrown an error due to the expired proposal");
+    } catch (error) {
+      assert(error.toString().includes("ProposalExpired"), "Expected ProposalExpired error");
+    }
+
+    // A third party cleans up the proposal and the rent goes back to the proposer
     await program.methods
-      .acceptOwnership()
+      .expireTransaction()
       .accounts({
-        vault: vaultPDA,
-        newOwner: newOwnerKeypair.publicKey,
+        vault,
+        pendingTransaction: transactionPDA(0, vault),
+        proposer: ownerKeypair.publicKey,
       })
-      .signers([newOwnerKeypair])
       .rpc();
-    
-    // Verify ownership transfer completed
-    const vaultAccount = await program.account.vault.fetch(vaultPDA);
-    assert.equal(
-      vaultAccount.owner.toString(),
-      newOwnerKeypair.publicKey.toString(),
-      "Owner should be updated to new owner"
-    );
+
+    const vaultAccount = await program.account.vault.fetch(vault);
+    assert.equal(await provider.connection.getAccountInfo(transactionPDA(0, vault)), null, "Transaction account should be closed");
+    assert.equal(vaultAccount.openTransactions, 0, "No transaction should be waiting for approval");
+    assert.notEqual(vaultAccount.maxWithdrawalLimit.toString(), "1000", "Expired proposal should not change the limit");
+  });
+
+  it("Revoking an approval drops a proposal back below its threshold", async () => {
+    const { vault, tokenAccount } = await initializeVault(7);
+    await enableMultisig(vault);
+
+    await program.methods
+      .setWithdrawalLimit(new anchor.BN(1000))
+      .accounts({
+        vault,
+        pendingTransaction: transactionPDA(0, vault),
+        owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    // Signer 1 approves and then changes their mind
+    for (const method of ["approveTransaction", "revokeApproval"]) {
+      await program.methods[method](new anchor.BN(0))
+        .accounts({
+          vault,
+          pendingTransaction: transactionPDA(0, vault),
+          signer: multisigSigner1.publicKey,
+        })
+        .signers([multisigSigner1])
+        .rpc();
+    }
+
+    const pendingTx = await program.account.pendingTransaction.fetch(transactionPDA(0, vault));
+    assert.equal(pendingTx.signers.length, 1, "Only the owner's approval should remain");
+
+    try {
+      await program.methods
+        .executeTransaction(new anchor.BN(0))
+        .accounts({
+          vault,
+          pendingTransaction: transactionPDA(0, vault),
+          vaultTokenAccount: tokenAccount,
+          tokenMint: mintKeypair.publicKey,
+          destinationTokenAccount: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      assert.fail("Should have thrown an error due to insufficient approvals");
+    } catch (error) {
+      assert(error.toString().includes("InsufficientApprovals"), "Expected InsufficientApprovals error");
+    }
+  });
+
+  it("Only the proposer or the owner can cancel a proposal", async () => {
+    const [vault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), ownerKeypair.publicKey.toBuffer(), new anchor.BN(7).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    // Another signer cannot withdraw the owner's proposal
+    try {
+      await program.methods
+        .cancelTransaction(new anchor.BN(0))
+        .accounts({
+          vault,
+          pendingTransaction: transactionPDA(0, vault),
+          signer: multisigSigner2.publicKey,
+        })
+        .signers([multisigSigner2])
+        .rpc();
+
+      assert.fail("Should have thrown an error due to unauthorized cancellation");
+    } catch (error) {
+      assert(error.toString().includes("Unauthorized"), "Expected Unauthorized error");
+    }
+
+    // The proposer can
+    await program.methods
+      .cancelTransaction(new anchor.BN(0))
+      .accounts({
+        vault,
+        pendingTransaction: transactionPDA(0, vault),
+        signer: ownerKeypair.publicKey,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    const vaultAccount = await program.account.vault.fetch(vault);
+    const pendingTx = await program.account.pendingTransaction.fetch(transactionPDA(0, vault));
+    assert(pendingTx.status.cancelled !== undefined, "Transaction should be cancelled");
+    assert.equal(vaultAccount.openTransactions, 0, "No transaction should be waiting for approval");
+  });
+
+  it("Proposals approved before an ownership change become stale", async () => {
+    const { vault, tokenAccount } = await initializeVault(8);
+    await enableMultisig(vault);
+    const executeAccounts = (txId) => ({
+      vault,
+      pendingTransaction: transactionPDA(txId, vault),
+      vaultTokenAccount: tokenAccount,
+      tokenMint: mintKeypair.publicKey,
+      destinationTokenAccount: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+    const approve = (txId) =>
+      program.methods
+        .approveTransaction(new anchor.BN(txId))
+        .accounts({
+          vault,
+          pendingTransaction: transactionPDA(txId, vault),
+          signer: multisigSigner1.publicKey,
+        })
+        .signers([multisigSigner1])
+        .rpc();
+
+    // A fully approved proposal that nobody has executed yet
+    await program.methods
+      .setWithdrawalLimit(new anchor.BN(1000))
+      .accounts({
+        vault,
+        pendingTransaction: transactionPDA(0, vault),
+        owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+    await approve(0);
+
+    // Ownership moves to a new owner in the meantime
+    await program.methods
+      .initiateOwnershipTransfer(newOwnerKeypair.publicKey)
+      .accounts({
+        vault,
+        pendingTransaction: transactionPDA(1, vault),
+        owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+    await approve(1);
+    await program.methods.executeTransaction(new anchor.BN(1)).accounts(executeAccounts(1)).rpc();
+
+    const vaultAccount = await program.account.vault.fetch(vault);
+    assert.equal(vaultAccount.owner.toString(), newOwnerKeypair.publicKey.toString(), "Owner should be rotated");
+
+    // The old owner's approval no longer counts
+    try {
+      await program.methods.executeTransaction(new anchor.BN(0)).accounts(executeAccounts(0)).rpc();
+
+      assert.fail("Should have thrown an error due to the stale proposal");
+    } catch (error) {
+      assert(error.toString().includes("StaleTransaction"), "Expected StaleTransaction error");
+    }
+
+    await program.methods
+      .expireTransaction()
+      .accounts({
+        vault,
+        pendingTransaction: transactionPDA(0, vault),
+        proposer: ownerKeypair.publicKey,
+      })
+      .rpc();
+    assert.equal(await provider.connection.getAccountInfo(transactionPDA(0, vault)), null, "Stale transaction should be closed");
+  });
+
+  it("Delegate proposals are not approvals and do not spend the allowance", async () => {
+    const { vault, tokenAccount } = await initializeVault(9);
+    const allowance = new anchor.BN(100);
+
+    await program.methods
+      .deposit(allowance, mintKeypair.publicKey)
+      .accounts({
+        vault,
+        vaultTokenAccount: tokenAccount,
+        tokenMint: mintKeypair.publicKey,
+        userTokenAccount: userTokenAccount,
+        owner: ownerKeypair.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+    await program.methods
+      .addDelegate(delegateKeypair.publicKey, allowance, new anchor.BN(0), new anchor.BN(0), DELEGATE_PERMISSION_WITHDRAW)
+      .accounts({
+        vault,
+        pendingTransaction: null,
+        owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+    await enableMultisig(vault);
+
+    // The delegate proposes a withdrawal of its whole allowance
+    await program.methods
+      .withdraw(allowance, mintKeypair.publicKey)
+      .accounts({
+        vault,
+        vaultTokenAccount: tokenAccount,
+        tokenMint: mintKeypair.publicKey,
+        userTokenAccount: delegateTokenAccount,
+        pendingTransaction: transactionPDA(0, vault),
+        owner: delegateKeypair.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([delegateKeypair])
+      .rpc();
+
+    const pendingTx = await program.account.pendingTransaction.fetch(transactionPDA(0, vault));
+    assert.equal(pendingTx.signers.length, 0, "The delegate should not count as an approval");
+
+    await program.methods
+      .cancelTransaction(new anchor.BN(0))
+      .accounts({
+        vault,
+        pendingTransaction: transactionPDA(0, vault),
+        signer: ownerKeypair.publicKey,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    // The cancelled proposal leaves the allowance untouched
+    const vaultAccount = await program.account.vault.fetch(vault);
+    assert.equal(vaultAccount.delegates[0].spent.toNumber(), 0, "Allowance should not be spent by a proposal");
+  });
+
+  it("Executes small withdrawals immediately under approval tiers", async () => {
+    const txId = 2;
+    const smallAmount = new anchor.BN(1000);
+
+    // Amounts up to 1000 tokens need a single signature, anything above uses the threshold of 2
+    await program.methods
+      .setApprovalTiers([{ maxAmount: smallAmount, threshold: 1 }])
+      .accounts({
+        vault: vaultPDA,
+        pendingTransaction: transactionPDA(txId),
+        owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    await program.methods
+      .approveTransaction(new anchor.BN(txId))
+      .accounts({
+        vault: vaultPDA,
+        pendingTransaction: transactionPDA(txId),
+        signer: multisigSigner1.publicKey,
+      })
+      .signers([multisigSigner1])
+      .rpc();
+
+    await program.methods
+      .executeTransaction(new anchor.BN(txId))
+      .accounts({
+        vault: vaultPDA,
+        pendingTransaction: transactionPDA(txId),
+        vaultTokenAccount: vaultTokenAccount.publicKey,
+        tokenMint: mintKeypair.publicKey,
+        destinationTokenAccount: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const userBalanceBefore = await provider.connection.getTokenAccountBalance(userTokenAccount);
+
+    // A small withdrawal does not create a pending transaction
+    await program.methods
+      .withdraw(smallAmount, mintKeypair.publicKey)
+      .accounts({
+        vault: vaultPDA,
+        vaultTokenAccount: vaultTokenAccount.publicKey,
+        tokenMint: mintKeypair.publicKey,
+        userTokenAccount: userTokenAccount,
+        pendingTransaction: null,
+        owner: ownerKeypair.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    const userBalance = await provider.connection.getTokenAccountBalance(userTokenAccount);
+    assert.equal(
+      Number(userBalance.value.amount) - Number(userBalanceBefore.value.amount),
+      smallAmount.toNumber(),
+      "Small withdrawal should execute immediately"
+    );
+  });
+
+  it("Sets a withdrawal limit", async () => {
+    const txId = 3;
+
+    // Resetting multisig to single signature is itself a multisig proposal
+    await program.methods
+      .setMultisig(1, [])
+      .accounts({
+        vault: vaultPDA,
+        pendingTransaction: transactionPDA(txId),
+        owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    let vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert.equal(vaultAccount.multisigThreshold, 2, "Threshold should not change before approval");
+
+    await program.methods
+      .approveTransaction(new anchor.BN(txId))
+      .accounts({
+        vault: vaultPDA,
+        pendingTransaction: transactionPDA(txId),
+        signer: multisigSigner1.publicKey,
+      })
+      .signers([multisigSigner1])
+      .rpc();
+
+    await program.methods
+      .executeTransaction(new anchor.BN(txId))
+      .accounts({
+        vault: vaultPDA,
+        pendingTransaction: transactionPDA(txId),
+        vaultTokenAccount: vaultTokenAccount.publicKey,
+        tokenMint: mintKeypair.publicKey,
+        destinationTokenAccount: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert.equal(vaultAccount.multisigThreshold, 1, "Threshold should be reset once approved");
+
+    // Set a withdrawal limit
+    await program.methods
+      .setWithdrawalLimit(withdrawalLimit)
+      .accounts({
+        vault: vaultPDA,
+        pendingTransaction: null,
+        owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+    
+    // Verify withdrawal limit was set
+    vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert.equal(vaultAccount.maxWithdrawalLimit.toNumber(), withdrawalLimit.toNumber(), "Withdrawal limit should be set");
+  });
+
+  it("Cannot withdraw more than the limit", async () => {
+    try {
+      await program.methods
+        .withdraw(exceedingAmount, mintKeypair.publicKey)
+        .accounts({
+          vault: vaultPDA,
+          vaultTokenAccount: vaultTokenAccount.publicKey,
+          tokenMint: mintKeypair.publicKey,
+          userTokenAccount: userTokenAccount,
+          pendingTransaction: null,
+          owner: ownerKeypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ownerKeypair])
+        .rpc();
+      
+      assert.fail("Should have thrown an error due to exceeding withdrawal limit");
+    } catch (error) {
+      assert(error.toString().includes("ExceedsWithdrawalLimit"), "Expected ExceedsWithdrawalLimit error");
+    }
+  });
+
+  it("Can withdraw up to the limit", async () => {
+    const userBalanceBefore = await provider.connection.getTokenAccountBalance(userTokenAccount);
+    const vaultBalanceBefore = await provider.connection.getTokenAccountBalance(vaultTokenAccount.publicKey);
+    
+    await program.methods
+      .withdraw(withdrawalLimit, mintKeypair.publicKey)
+      .accounts({
+        vault: vaultPDA,
+        vaultTokenAccount: vaultTokenAccount.publicKey,
+        tokenMint: mintKeypair.publicKey,
+        userTokenAccount: userTokenAccount,
+        pendingTransaction: null,
+        owner: ownerKeypair.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    // Verify token balances
+    const userBalance = await provider.connection.getTokenAccountBalance(userTokenAccount);
+    const vaultBalance = await provider.connection.getTokenAccountBalance(vaultTokenAccount.publicKey);
+    
+    assert.equal(
+      Number(userBalance.value.amount) - Number(userBalanceBefore.value.amount),
+      withdrawalLimit.toNumber(),
+      "User balance should increase by withdraw amount"
+    );
+    assert.equal(
+      Number(vaultBalanceBefore.value.amount) - Number(vaultBalance.value.amount),
+      withdrawalLimit.toNumber(),
+      "Vault balance should decrease by withdraw amount"
+    );
+  });
+
+  it("Cannot exceed the withdrawal budget for the current window", async () => {
+    const windowLimit = new anchor.BN(1000);
+    const windowWithdraw = new anchor.BN(600);
+    const windowAccounts = {
+      vault: vaultPDA,
+      pendingTransaction: null,
+      owner: ownerKeypair.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+    const withdrawAccounts = {
+      vault: vaultPDA,
+      vaultTokenAccount: vaultTokenAccount.publicKey,
+      tokenMint: mintKeypair.publicKey,
+      userTokenAccount: userTokenAccount,
+      pendingTransaction: null,
+      owner: ownerKeypair.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+
+    // Allow at most 1000 tokens per hour
+    await program.methods
+      .setWithdrawalWindow(new anchor.BN(3600), windowLimit)
+      .accounts(windowAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    // The first withdrawal fits in the budget
+    await program.methods
+      .withdraw(windowWithdraw, mintKeypair.publicKey)
+      .accounts(withdrawAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    const vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert.equal(
+      vaultAccount.windowSpent.reduce((total, spent) => total + spent.toNumber(), 0),
+      windowWithdraw.toNumber(),
+      "Window should track the spent amount"
+    );
+
+    // A repeated withdrawal within the same window is refused
+    try {
+      await program.methods
+        .withdraw(windowWithdraw, mintKeypair.publicKey)
+        .accounts(withdrawAccounts)
+        .signers([ownerKeypair])
+        .rpc();
+
+      assert.fail("Should have thrown an error due to exceeding the window budget");
+    } catch (error) {
+      assert(error.toString().includes("ExceedsWindowBudget"), "Expected ExceedsWindowBudget error");
+    }
+
+    // Remove the window budget again for the following tests
+    await program.methods
+      .setWithdrawalWindow(new anchor.BN(0), new anchor.BN("18446744073709551615"))
+      .accounts(windowAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+  });
+
+  it("Withdrawal budget keeps counting across the window boundary", async () => {
+    const windowAccounts = {
+      vault: vaultPDA,
+      pendingTransaction: null,
+      owner: ownerKeypair.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+    const withdrawAccounts = {
+      vault: vaultPDA,
+      vaultTokenAccount: vaultTokenAccount.publicKey,
+      tokenMint: mintKeypair.publicKey,
+      userTokenAccount: userTokenAccount,
+      pendingTransaction: null,
+      owner: ownerKeypair.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+
+    // Allow at most 1000 tokens per 6 seconds
+    await program.methods
+      .setWithdrawalWindow(new anchor.BN(6), new anchor.BN(1000))
+      .accounts(windowAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    await program.methods
+      .withdraw(new anchor.BN(400), mintKeypair.publicKey)
+      .accounts(withdrawAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    // Spend the rest of the budget late in the window
+    await new Promise((resolve) => setTimeout(resolve, 4000));
+    await program.methods
+      .withdraw(new anchor.BN(600), mintKeypair.publicKey)
+      .accounts(withdrawAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    // Past the first window's end, the recent withdrawal still counts
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+    try {
+      await program.methods
+        .withdraw(new anchor.BN(600), mintKeypair.publicKey)
+        .accounts(withdrawAccounts)
+        .signers([ownerKeypair])
+        .rpc();
+
+      assert.fail("Should have thrown an error due to exceeding the window budget");
+    } catch (error) {
+      assert(error.toString().includes("ExceedsWindowBudget"), "Expected ExceedsWindowBudget error");
+    }
+
+    await program.methods
+      .setWithdrawalWindow(new anchor.BN(0), new anchor.BN("18446744073709551615"))
+      .accounts(windowAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+  });
+
+  it("Cannot withdraw to an allowlisted destination before its delay passes", async () => {
+    const allowlistAccounts = {
+      vault: vaultPDA,
+      pendingTransaction: null,
+      owner: ownerKeypair.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+
+
+    // Require allowlisted destinations with a five second delay
+    await program.methods
+      .setAllowlist(true, allowlistDelay)
+      .accounts(allowlistAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    await program.methods
+      .addAllowlistEntry(userTokenAccount)
+      .accounts(allowlistAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    const vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert.equal(vaultAccount.allowlist.length, 1, "Should have one allowlist entry");
+    assert(vaultAccount.allowlist[0].activeAt.toNumber() > Date.now() / 1000, "Entry should not be active yet");
+
+    // The new entry is not usable yet
+    try {
+      await program.methods
+        .withdraw(new anchor.BN(1), mintKeypair.publicKey)
+        .accounts({
+          vault: vaultPDA,
+          vaultTokenAccount: vaultTokenAccount.publicKey,
+          tokenMint: mintKeypair.publicKey,
+          userTokenAccount: userTokenAccount,
+          pendingTransaction: null,
+          owner: ownerKeypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ownerKeypair])
+        .rpc();
+
+      assert.fail("Should have thrown an error due to the inactive allowlist entry");
+    } catch (error) {
+      assert(error.toString().includes("DestinationNotAllowed"), "Expected DestinationNotAllowed error");
+    }
+
+  });
+
+  it("Disabling the allowlist waits for the allowlist delay", async () => {
+    const allowlistAccounts = {
+      vault: vaultPDA,
+      pendingTransaction: null,
+      owner: ownerKeypair.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+    const withdrawOne = () =>
+      program.methods
+        .withdraw(new anchor.BN(1), mintKeypair.publicKey)
+        .accounts({
+          vault: vaultPDA,
+          vaultTokenAccount: vaultTokenAccount.publicKey,
+          tokenMint: mintKeypair.publicKey,
+          userTokenAccount: userTokenAccount,
+          pendingTransaction: null,
+          owner: ownerKeypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ownerKeypair])
+        .rpc();
+
+    await program.methods
+      .removeAllowlistEntry(userTokenAccount)
+      .accounts(allowlistAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+    await program.methods
+      .setAllowlist(false, allowlistDelay)
+      .accounts(allowlistAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    const vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert.equal(vaultAccount.allowlistEnabled, true, "Allowlist should stay enabled during the delay");
+    assert(vaultAccount.pendingAllowlist !== null, "Disabling should be pending");
+
+    // The list is still enforced until the delay passes
+    try {
+      await withdrawOne();
+
+      assert.fail("Should have thrown an error due to the allowlist");
+    } catch (error) {
+      assert(error.toString().includes("DestinationNotAllowed"), "Expected DestinationNotAllowed error");
+    }
+
+    // Afterwards the allowlist is off for the following tests
+    await new Promise((resolve) => setTimeout(resolve, (allowlistDelay.toNumber() + 1) * 1000));
+    await withdrawOne();
+  });
+
+  it("Announced withdrawals wait for the delay and can be cancelled", async () => {
+    const requestId = 0;
+    const delayAccounts = {
+      vault: vaultPDA,
+      pendingTransaction: null,
+      owner: ownerKeypair.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+
+    await program.methods
+      .setWithdrawalDelay(new anchor.BN(3600))
+      .accounts(delayAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    // Immediate withdrawals are no longer possible
+    try {
+      await program.methods
+        .withdraw(new anchor.BN(1), mintKeypair.publicKey)
+        .accounts({
+          vault: vaultPDA,
+          vaultTokenAccount: vaultTokenAccount.publicKey,
+          tokenMint: mintKeypair.publicKey,
+          userTokenAccount: userTokenAccount,
+          pendingTransaction: null,
+          owner: ownerKeypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ownerKeypair])
+        .rpc();
+
+      assert.fail("Should have thrown an error due to the withdrawal delay");
+    } catch (error) {
+      assert(error.toString().includes("WithdrawalDelayRequired"), "Expected WithdrawalDelayRequired error");
+    }
+
+    // Announce the withdrawal
+    await program.methods
+      .requestWithdrawal(new anchor.BN(1), mintKeypair.publicKey)
+      .accounts({
+        vault: vaultPDA,
+        withdrawalRequest: withdrawalRequestPDA(requestId),
+        destinationTokenAccount: userTokenAccount,
+        vaultTokenAccount: vaultTokenAccount.publicKey,
+        requester: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    const request = await program.account.withdrawalRequest.fetch(withdrawalRequestPDA(requestId));
+    let vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert.equal(
+      request.executableAt.toNumber(),
+      request.requestedAt.toNumber() + 3600,
+      "Request should become executable after the delay"
+    );
+    assert.equal(vaultAccount.openWithdrawalRequests, 1, "Request should be waiting for execution");
+
+    // It cannot be executed during the delay
+    try {
+      await program.methods
+        .executeWithdrawal(mintKeypair.publicKey)
+        .accounts({
+          vault: vaultPDA,
+          withdrawalRequest: withdrawalRequestPDA(requestId),
+          vaultTokenAccount: vaultTokenAccount.publicKey,
+          tokenMint: mintKeypair.publicKey,
+          destinationTokenAccount: userTokenAccount,
+          requester: ownerKeypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      assert.fail("Should have thrown an error due to the pending delay");
+    } catch (error) {
+      assert(error.toString().includes("WithdrawalNotReady"), "Expected WithdrawalNotReady error");
+    }
+
+    // The owner cancels the request during the delay
+    await program.methods
+      .cancelWithdrawal()
+      .accounts({
+        vault: vaultPDA,
+        withdrawalRequest: withdrawalRequestPDA(requestId),
+        requester: ownerKeypair.publicKey,
+        signer: ownerKeypair.publicKey,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    assert.equal(
+      await provider.connection.getAccountInfo(withdrawalRequestPDA(requestId)),
+      null,
+      "Withdrawal request should be closed"
+    );
+    vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert.equal(vaultAccount.openWithdrawalRequests, 0, "No request should be waiting for execution");
+
+    // Remove the delay again for the following tests
+    await program.methods
+      .setWithdrawalDelay(new anchor.BN(0))
+      .accounts(delayAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+  });
+
+  it("Releases vested tokens to the beneficiary", async () => {
+    const scheduleId = 0;
+    const grantAmount = new anchor.BN(500);
+    const now = Math.floor(Date.now() / 1000);
+    const scheduleAccounts = {
+      vault: vaultPDA,
+      vestingSchedule: vestingSchedulePDA(scheduleId),
+      vaultTokenAccount: vaultTokenAccount.publicKey,
+      beneficiaryTokenAccount: delegateTokenAccount,
+      owner: ownerKeypair.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+
+    // Schedules cannot vest retroactively
+    try {
+      await program.methods
+        .createVestingSchedule(
+          delegateKeypair.publicKey,
+          new anchor.BN(now - 200), // 開始
+          new anchor.BN(now - 100), // クリフ
+          new anchor.BN(now - 1), // 終了
+          grantAmount,
+          true
+        )
+        .accounts(scheduleAccounts)
+        .signers([ownerKeypair])
+        .rpc();
+
+      assert.fail("Should have thrown an error due to the back-dated schedule");
+    } catch (error) {
+      assert(error.toString().includes("InvalidVestingSchedule"), "Expected InvalidVestingSchedule error");
+    }
+
+    // A short schedule that fully vests a few seconds from now
+    await program.methods
+      .createVestingSchedule(
+        delegateKeypair.publicKey,
+        new anchor.BN(now + 3), // 開始
+        new anchor.BN(now + 3), // クリフ
+        new anchor.BN(now + 5), // 終了
+        grantAmount,
+        true
+      )
+      .accounts(scheduleAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    let vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert.equal(vaultAccount.reservedAmount.toNumber(), grantAmount.toNumber(), "Grant should be reserved");
+
+    await new Promise((resolve) => setTimeout(resolve, 7000));
+
+    const beneficiaryBalanceBefore = await provider.connection.getTokenAccountBalance(delegateTokenAccount);
+
+    // Anyone can release to the beneficiary
+    await program.methods
+      .releaseVested()
+      .accounts({
+        vault: vaultPDA,
+        vestingSchedule: vestingSchedulePDA(scheduleId),
+        vaultTokenAccount: vaultTokenAccount.publicKey,
+        tokenMint: mintKeypair.publicKey,
+        beneficiaryTokenAccount: delegateTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const beneficiaryBalance = await provider.connection.getTokenAccountBalance(delegateTokenAccount);
+    assert.equal(
+      Number(beneficiaryBalance.value.amount) - Number(beneficiaryBalanceBefore.value.amount),
+      grantAmount.toNumber(),
+      "Beneficiary should receive the vested amount"
+    );
+
+    vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert.equal(vaultAccount.reservedAmount.toNumber(), 0, "Reservation should be released");
+
+    // The completed schedule can be closed
+    await program.methods
+      .closeVestingSchedule()
+      .accounts({
+        vestingSchedule: vestingSchedulePDA(scheduleId),
+        funder: ownerKeypair.publicKey,
+      })
+      .rpc();
+
+    assert.equal(
+      await provider.connection.getAccountInfo(vestingSchedulePDA(scheduleId)),
+      null,
+      "Vesting schedule should be closed"
+    );
+  });
+
+  it("Pays accrued periods of a payment stream", async () => {
+    const streamId = 0;
+    const amountPerPeriod = new anchor.BN(10);
+    const now = Math.floor(Date.now() / 1000);
+
+    const createAccounts = {
+      vault: vaultPDA,
+      paymentStream: paymentStreamPDA(streamId),
+      recipientTokenAccount: delegateTokenAccount,
+      vaultTokenAccount: vaultTokenAccount.publicKey,
+      owner: ownerKeypair.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+
+    // Streams cannot accrue retroactively
+    try {
+      await program.methods
+        .createPaymentStream(amountPerPeriod, new anchor.BN(60), new anchor.BN(now - 600), new anchor.BN(now - 1))
+        .accounts(createAccounts)
+        .signers([ownerKeypair])
+        .rpc();
+
+      assert.fail("Should have thrown an error due to the back-dated stream");
+    } catch (error) {
+      assert(error.toString().includes("InvalidPaymentStream"), "Expected InvalidPaymentStream error");
+    }
+
+    // Six one-second periods starting shortly
+    await program.methods
+      .createPaymentStream(amountPerPeriod, new anchor.BN(1), new anchor.BN(now + 2), new anchor.BN(now + 8))
+      .accounts(createAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    let vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert.equal(vaultAccount.openPaymentStreams, 1, "Stream should be open");
+
+    // A limit of five periods per claim splits the backlog
+    const limitAccounts = {
+      vault: vaultPDA,
+      pendingTransaction: null,
+      owner: ownerKeypair.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+    await program.methods
+      .setWithdrawalLimit(new anchor.BN(50))
+      .accounts(limitAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 9000));
+
+    const recipientBalanceBefore = await provider.connection.getTokenAccountBalance(delegateTokenAccount);
+    const streamAccounts = {
+      vault: vaultPDA,
+      paymentStream: paymentStreamPDA(streamId),
+      vaultTokenAccount: vaultTokenAccount.publicKey,
+      tokenMint: mintKeypair.publicKey,
+      recipientTokenAccount: delegateTokenAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    // Anyone can crank the payment, and the periods over the limit stay claimable
+    await program.methods.claimStream().accounts(streamAccounts).rpc();
+
+    let recipientBalance = await provider.connection.getTokenAccountBalance(delegateTokenAccount);
+    assert.equal(
+      Number(recipientBalance.value.amount) - Number(recipientBalanceBefore.value.amount),
+      50,
+      "Only the periods within the limit should be paid"
+    );
+
+    await program.methods.claimStream().accounts(streamAccounts).rpc();
+
+    recipientBalance = await provider.connection.getTokenAccountBalance(delegateTokenAccount);
+    const paid = Number(recipientBalance.value.amount) - Number(recipientBalanceBefore.value.amount);
+    assert(paid > 50, "Remaining periods should be paid by the next claim");
+    assert.equal(paid % amountPerPeriod.toNumber(), 0, "Only whole periods should be paid");
+
+    // Nothing is left to claim right away
+    try {
+      await program.methods.claimStream().accounts(streamAccounts).rpc();
+      assert.fail("Should have thrown an error as nothing has accrued");
+    } catch (error) {
+      assert(error.toString().includes("NothingAccrued"), "Expected NothingAccrued error");
+    }
+
+    // The owner stops the stream and gets the rent back
+    await program.methods
+      .cancelPaymentStream()
+      .accounts({
+        vault: vaultPDA,
+        paymentStream: paymentStreamPDA(streamId),
+        funder: ownerKeypair.publicKey,
+        owner: ownerKeypair.publicKey,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert.equal(vaultAccount.openPaymentStreams, 0, "No stream should be open");
+
+    // Restore the withdrawal limit for the following tests
+    await program.methods
+      .setWithdrawalLimit(withdrawalLimit)
+      .accounts(limitAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+  });
+
+  it("Guardian can pause withdrawals but only the owner can unpause", async () => {
+    const guardianKeypair = anchor.web3.Keypair.generate();
+    const guardianAccounts = {
+      vault: vaultPDA,
+      pendingTransaction: null,
+      owner: ownerKeypair.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+
+    await program.methods
+      .setGuardians([guardianKeypair.publicKey])
+      .accounts(guardianAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    // The guardian halts the vault
+    let pausedEvent = null;
+    const listener = program.addEventListener("Paused", (event) => {
+      pausedEvent = event;
+    });
+    await program.methods
+      .pause()
+      .accounts({
+        vault: vaultPDA,
+        signer: guardianKeypair.publicKey,
+      })
+      .signers([guardianKeypair])
+      .rpc();
+
+    let vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert.equal(vaultAccount.paused, true, "Vault should be paused");
+
+    // Watchers learn who paused the vault from the emitted event
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+    await program.removeEventListener(listener);
+    assert(pausedEvent !== null, "Paused event should be emitted");
+    assert.equal(pausedEvent.actor.toString(), guardianKeypair.publicKey.toString());
+
+    // Withdrawals are halted
+    try {
+      await program.methods
+        .withdraw(new anchor.BN(1), mintKeypair.publicKey)
+        .accounts({
+          vault: vaultPDA,
+          vaultTokenAccount: vaultTokenAccount.publicKey,
+          tokenMint: mintKeypair.publicKey,
+          userTokenAccount: userTokenAccount,
+          pendingTransaction: null,
+          owner: ownerKeypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ownerKeypair])
+        .rpc();
+
+      assert.fail("Should have thrown an error as the vault is paused");
+    } catch (error) {
+      assert(error.toString().includes("VaultPaused"), "Expected VaultPaused error");
+    }
+
+    // The owner resumes the vault
+    await program.methods
+      .unpause()
+      .accounts(guardianAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert.equal(vaultAccount.paused, false, "Vault should be resumed");
+  });
+
+  it("Beneficiary cannot claim while the owner is active", async () => {
+    const beneficiaryKeypair = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .setInheritance(beneficiaryKeypair.publicKey, new anchor.BN(365 * 24 * 60 * 60))
+      .accounts({
+        vault: vaultPDA,
+        pendingTransaction: null,
+        owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    // The owner checks in explicitly
+    await program.methods
+      .heartbeat()
+      .accounts({
+        vault: vaultPDA,
+        owner: ownerKeypair.publicKey,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    const vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert.equal(
+      vaultAccount.beneficiary.toString(),
+      beneficiaryKeypair.publicKey.toString(),
+      "Beneficiary should be set"
+    );
+    assert(vaultAccount.lastOwnerActivity.toNumber() > 0, "Owner activity should be recorded");
+
+    try {
+      await program.methods
+        .claimInheritance({ takeOwnership: {} })
+        .accounts({
+          vault: vaultPDA,
+          vaultTokenAccount: vaultTokenAccount.publicKey,
+          tokenMint: mintKeypair.publicKey,
+          beneficiaryTokenAccount: null,
+          beneficiary: beneficiaryKeypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([beneficiaryKeypair])
+        .rpc();
+
+      assert.fail("Should have thrown an error as the owner is still active");
+    } catch (error) {
+      assert(error.toString().includes("OwnerStillActive"), "Expected OwnerStillActive error");
+    }
+  });
+
+  it("Guardians can recover ownership of a vault", async () => {
+    // Use the second vault so the main owner stays unchanged
+    const [secondVaultPDA] = await PublicKey.findProgramAddress(
+      [Buffer.from("vault"), ownerKeypair.publicKey.toBuffer(), secondVaultId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const guardian1 = anchor.web3.Keypair.generate();
+    const guardian2 = anchor.web3.Keypair.generate();
+    const recoveredOwner = anchor.web3.Keypair.generate();
+    const configAccounts = {
+      vault: secondVaultPDA,
+      pendingTransaction: null,
+      owner: ownerKeypair.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+
+    // 2-of-2 guardians without a delay
+    await program.methods
+      .setGuardians([guardian1.publicKey, guardian2.publicKey])
+      .accounts(configAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+    await program.methods
+      .setRecovery(2, new anchor.BN(0))
+      .accounts(configAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    await program.methods
+      .proposeRecovery(recoveredOwner.publicKey)
+      .accounts({ vault: secondVaultPDA, signer: guardian1.publicKey })
+      .signers([guardian1])
+      .rpc();
+
+    // One approval is not enough
+    try {
+      await program.methods
+        .finalizeRecovery()
+        .accounts({ vault: secondVaultPDA, signer: guardian1.publicKey })
+        .signers([guardian1])
+        .rpc();
+
+      assert.fail("Should have thrown an error due to missing approvals");
+    } catch (error) {
+      assert(error.toString().includes("RecoveryNotReady"), "Expected RecoveryNotReady error");
+    }
+
+    await program.methods
+      .approveRecovery()
+      .accounts({ vault: secondVaultPDA, signer: guardian2.publicKey })
+      .signers([guardian2])
+      .rpc();
+
+    await program.methods
+      .finalizeRecovery()
+      .accounts({ vault: secondVaultPDA, signer: guardian2.publicKey })
+      .signers([guardian2])
+      .rpc();
+
+    const secondVaultAccount = await program.account.vault.fetch(secondVaultPDA);
+    assert.equal(
+      secondVaultAccount.owner.toString(),
+      recoveredOwner.publicKey.toString(),
+      "Ownership should be recovered"
+    );
+    assert.equal(secondVaultAccount.pendingRecovery, null, "Recovery should be completed");
+  });
+
+  it("Records deposits in the vault's audit log", async () => {
+    // Use a separate vault, since every later operation would need the log
+    const auditVaultId = new anchor.BN(2);
+    const [auditVaultPDA] = await PublicKey.findProgramAddress(
+      [Buffer.from("vault"), ownerKeypair.publicKey.toBuffer(), auditVaultId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [auditLogPDA] = await PublicKey.findProgramAddress(
+      [Buffer.from("audit_log"), auditVaultPDA.toBuffer()],
+      program.programId
+    );
+    const auditVaultTokenAccount = anchor.web3.Keypair.generate();
+    const amount = new anchor.BN(10);
+
+    await program.methods
+      .initialize(auditVaultId)
+      .accounts({
+        vault: auditVaultPDA,
+        vaultTokenAccount: auditVaultTokenAccount.publicKey,
+        mint: mintKeypair.publicKey,
+        owner: ownerKeypair.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([ownerKeypair, auditVaultTokenAccount])
+      .rpc();
+
+    await program.methods
+      .initAuditLog()
+      .accounts({
+        vault: auditVaultPDA,
+        auditLog: auditLogPDA,
+        owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    const depositAccounts = {
+      vault: auditVaultPDA,
+      auditLog: auditLogPDA,
+      vaultTokenAccount: auditVaultTokenAccount.publicKey,
+      tokenMint: mintKeypair.publicKey,
+      userTokenAccount: userTokenAccount,
+      owner: ownerKeypair.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    await program.methods
+      .deposit(amount, mintKeypair.publicKey)
+      .accounts(depositAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    // Once the log exists, it cannot be skipped
+    try {
+      await program.methods
+        .deposit(amount, mintKeypair.publicKey)
+        .accounts({ ...depositAccounts, auditLog: null })
+        .signers([ownerKeypair])
+        .rpc();
+
+      assert.fail("Should have thrown an error as the audit log is missing");
+    } catch (error) {
+      assert(error.toString().includes("AuditLogRequired"), "Expected AuditLogRequired error");
+    }
+
+    const entries = await program.methods
+      .readAuditLog(new anchor.BN(0), 10)
+      .accounts({ auditLog: auditLogPDA })
+      .view();
+
+    assert.equal(entries.length, 1, "Only the successful deposit should be recorded");
+    assert.equal(entries[0].sequence.toNumber(), 0);
+    assert.equal(entries[0].action, 1, "Entry should be a deposit");
+    assert.equal(entries[0].actor.toString(), ownerKeypair.publicKey.toString());
+    assert.equal(entries[0].counterparty.toString(), userTokenAccount.toString());
+    assert.equal(entries[0].amount.toNumber(), amount.toNumber());
+  });
+
+  it("Records each setting with its own action and closes the log with the vault", async () => {
+    const [auditVaultPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), ownerKeypair.publicKey.toBuffer(), new anchor.BN(2).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [auditLogPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("audit_log"), auditVaultPDA.toBuffer()],
+      program.programId
+    );
+    const auditVaultAccount = await program.account.vault.fetch(auditVaultPDA);
+
+    await program.methods
+      .setWithdrawalLimit(new anchor.BN(500))
+      .accounts({
+        vault: auditVaultPDA,
+        auditLog: auditLogPDA,
+        pendingTransaction: null,
+        owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    const entries = await program.methods
+      .readAuditLog(new anchor.BN(1), 10)
+      .accounts({ auditLog: auditLogPDA })
+      .view();
+
+    assert.equal(entries.length, 1, "The limit change should be recorded");
+    assert.equal(entries[0].action, 13, "Entry should be a withdrawal limit change");
+    assert.equal(entries[0].counterparty.toString(), mintKeypair.publicKey.toString());
+    assert.equal(entries[0].amount.toNumber(), 500);
+
+    // Closing the vault returns the log's rent as well
+    await program.methods
+      .closeVault()
+      .accounts({
+        vault: auditVaultPDA,
+        auditLog: auditLogPDA,
+        vaultTokenAccount: auditVaultAccount.tokenAccount,
+        tokenMint: mintKeypair.publicKey,
+        destinationTokenAccount: userTokenAccount,
+        pendingTransaction: null,
+        approvedTransaction: null,
+        owner: ownerKeypair.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    assert.equal(await provider.connection.getAccountInfo(auditLogPDA), null, "Audit log should be closed");
+    assert.equal(await provider.connection.getAccountInfo(auditVaultPDA), null, "Vault should be closed");
+  });
+
+  it("Holds a registered mint with its own withdrawal limit", async () => {
+    // Use a separate vault so the main vault can still be closed at the end
+    const multiMintVaultId = new anchor.BN(3);
+    const [multiMintVaultPDA] = await PublicKey.findProgramAddress(
+      [Buffer.from("vault"), ownerKeypair.publicKey.toBuffer(), multiMintVaultId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const multiMintVaultTokenAccount = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .initialize(multiMintVaultId)
+      .accounts({
+        vault: multiMintVaultPDA,
+        vaultTokenAccount: multiMintVaultTokenAccount.publicKey,
+        mint: mintKeypair.publicKey,
+        owner: ownerKeypair.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([ownerKeypair, multiMintVaultTokenAccount])
+      .rpc();
+
+    // A second token with different decimals
+    const secondMint = await createMint(
+      provider.connection,
+      provider.wallet.payer,
+      provider.wallet.publicKey,
+      null,
+      6
+    );
+    const ownerSecondTokenAccount = await createAccount(
+      provider.connection,
+      provider.wallet.payer,
+      secondMint,
+      ownerKeypair.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      provider.wallet.payer,
+      secondMint,
+      ownerSecondTokenAccount,
+      provider.wallet.publicKey,
+      1000
+    );
+
+    const [secondMintTokenAccount] = await PublicKey.findProgramAddress(
+      [Buffer.from("token"), multiMintVaultPDA.toBuffer(), secondMint.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .registerMint()
+      .accounts({
+        vault: multiMintVaultPDA,
+        mint: secondMint,
+        tokenAccount: secondMintTokenAccount,
+        owner: ownerKeypair.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    await program.methods
+      .deposit(new anchor.BN(1000), secondMint)
+      .accounts({
+        vault: multiMintVaultPDA,
+        vaultTokenAccount: secondMintTokenAccount,
+        tokenMint: secondMint,
+        userTokenAccount: ownerSecondTokenAccount,
+        owner: ownerKeypair.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    // The limit only applies to the second mint
+    await program.methods
+      .setMintWithdrawalLimit(secondMint, new anchor.BN(400))
+      .accounts({
+        vault: multiMintVaultPDA,
+        pendingTransaction: null,
+        owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    const withdrawAccounts = {
+      vault: multiMintVaultPDA,
+      vaultTokenAccount: secondMintTokenAccount,
+      tokenMint: secondMint,
+      userTokenAccount: ownerSecondTokenAccount,
+      pendingTransaction: null,
+      owner: ownerKeypair.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+
+    try {
+      await program.methods
+        .withdraw(new anchor.BN(500), secondMint)
+        .accounts(withdrawAccounts)
+        .signers([ownerKeypair])
+        .rpc();
+
+      assert.fail("Should have thrown an error due to the per-mint limit");
+    } catch (error) {
+      assert(error.toString().includes("ExceedsWithdrawalLimit"), "Expected ExceedsWithdrawalLimit error");
+    }
+
+    await program.methods
+      .withdraw(new anchor.BN(400), secondMint)
+      .accounts(withdrawAccounts)
+      .signers([ownerKeypair])
+      .rpc();
+
+    const vaultBalance = await provider.connection.getTokenAccountBalance(secondMintTokenAccount);
+    assert.equal(Number(vaultBalance.value.amount), 600, "Vault should hold the rest of the second mint");
+
+    const multiMintVaultAccount = await program.account.vault.fetch(multiMintVaultPDA);
+    assert.equal(multiMintVaultAccount.mints.length, 1, "Second mint should be registered");
+    assert.equal(multiMintVaultAccount.mints[0].maxWithdrawalLimit.toNumber(), 400);
+    assert.equal(
+      multiMintVaultAccount.maxWithdrawalLimit.toString(),
+      "18446744073709551615",
+      "Primary mint limit should be unchanged"
+    );
+  });
+
+  it("Records the received amount of Token-2022 transfer-fee mints", async () => {
+    // 1% transfer fee
+    const feeMint = await createToken2022Mint([ExtensionType.TransferFeeConfig], (mint) => [
+      createInitializeTransferFeeConfigInstruction(
+        mint,
+        provider.wallet.publicKey,
+        provider.wallet.publicKey,
+        100,
+        BigInt(1000000),
+        TOKEN_2022_PROGRAM_ID
+      ),
+    ]);
+    const ownerFeeTokenAccount = await createAccount(
+      provider.connection,
+      provider.wallet.payer,
+      feeMint,
+      ownerKeypair.publicKey,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await mintTo(
+      provider.connection,
+      provider.wallet.payer,
+      feeMint,
+      ownerFeeTokenAccount,
+      provider.wallet.publicKey,
+      10000,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const feeVaultId = new anchor.BN(4);
+    const [feeVaultPDA] = await PublicKey.findProgramAddress(
+      [Buffer.from("vault"), ownerKeypair.publicKey.toBuffer(), feeVaultId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const feeVaultTokenAccount = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .initialize(feeVaultId)
+      .accounts({
+        vault: feeVaultPDA,
+        vaultTokenAccount: feeVaultTokenAccount.publicKey,
+        mint: feeMint,
+        owner: ownerKeypair.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([ownerKeypair, feeVaultTokenAccount])
+      .rpc();
+
+    let depositedEvent = null;
+    const listener = program.addEventListener("Deposited", (event) => {
+      depositedEvent = event;
+    });
+
+    await program.methods
+      .deposit(new anchor.BN(10000), feeMint)
+      .accounts({
+        vault: feeVaultPDA,
+        vaultTokenAccount: feeVaultTokenAccount.publicKey,
+        tokenMint: feeMint,
+        userTokenAccount: ownerFeeTokenAccount,
+        owner: ownerKeypair.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+
+    const vaultBalance = await provider.connection.getTokenAccountBalance(feeVaultTokenAccount.publicKey);
+    assert.equal(Number(vaultBalance.value.amount), 9900, "Vault should receive the amount minus the fee");
+
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+    await program.removeEventListener(listener);
+    assert(depositedEvent !== null, "Deposited event should be emitted");
+    assert.equal(depositedEvent.amount.toNumber(), 10000);
+    assert.equal(depositedEvent.received.toNumber(), 9900, "Received amount should exclude the fee");
+  });
+
+  it("Rejects Token-2022 mints with unsafe extensions", async () => {
+    // A permanent delegate could move tokens out of the vault
+    const delegatedMint = await createToken2022Mint([ExtensionType.PermanentDelegate], (mint) => [
+      createInitializePermanentDelegateInstruction(mint, provider.wallet.publicKey, TOKEN_2022_PROGRAM_ID),
+    ]);
+
+    const unsafeVaultId = new anchor.BN(5);
+    const [unsafeVaultPDA] = await PublicKey.findProgramAddress(
+      [Buffer.from("vault"), ownerKeypair.publicKey.toBuffer(), unsafeVaultId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const unsafeVaultTokenAccount = anchor.web3.Keypair.generate();
+
+    try {
+      await program.methods
+        .initialize(unsafeVaultId)
+        .accounts({
+          vault: unsafeVaultPDA,
+          vaultTokenAccount: unsafeVaultTokenAccount.publicKey,
+          mint: delegatedMint,
+          owner: ownerKeypair.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([ownerKeypair, unsafeVaultTokenAccount])
+        .rpc();
+
+      assert.fail("Should have thrown an error due to the permanent delegate");
+    } catch (error) {
+      assert(error.toString().includes("UnsupportedMintExtension"), "Expected UnsupportedMintExtension error");
+    }
+  });
+
+  it("Initiates ownership transfer", async () => {
+    // Initiate ownership transfer
+    await program.methods
+      .initiateOwnershipTransfer(newOwnerKeypair.publicKey)
+      .accounts({
+        vault: vaultPDA,
+        pendingTransaction: null,
+        owner: ownerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ownerKeypair])
+      .rpc();
+    
+    // Verify pending ownership transfer
+    const vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert(vaultAccount.transferOwnershipTo !== null, "Should have pending ownership transfer");
+    assert.equal(
+      vaultAccount.transferOwnershipTo.toString(),
+      newOwnerKeypair.publicKey.toString(),
+      "Pending owner should match"
+    );
+  });
+
+  it("Cannot accept ownership if not the intended new owner", async () => {
+    try {
+      await program.methods
+        .acceptOwnership()
+        .accounts({
+          vault: vaultPDA,
+          newOwner: delegateKeypair.publicKey,
+        })
+        .signers([delegateKeypair])
+        .rpc();
+      
+      assert.fail("Should have thrown an error due to unauthorized access");
+    } catch (error) {
+      assert(error.toString().includes("Unauthorized"), "Expected Unauthorized error");
+    }
+  });
+
+  it("New owner can accept ownership", async () => {
+    await program.methods
+      .acceptOwnership()
+      .accounts({
+        vault: vaultPDA,
+        newOwner: newOwnerKeypair.publicKey,
+      })
+      .signers([newOwnerKeypair])
+      .rpc();
+    
+    // Verify ownership transfer completed
+    const vaultAccount = await program.account.vault.fetch(vaultPDA);
+    assert.equal(
+      vaultAccount.owner.toString(),
+      newOwnerKeypair.publicKey.toString(),
+      "Owner should be updated to new owner"
+    );
     assert.equal(vaultAccount.transferOwnershipTo, null, "Pending transfer should be cleared");
     assert.equal(vaultAccount.delegates.length, 0, "Delegates should be cleared");
+    assert.equal(
+      vaultAccount.creator.toString(),
+      ownerKeypair.publicKey.toString(),
+      "Creator (PDA seed) should not change with ownership"
+    );
   });
 
   it("New owner can withdraw from vault", async () => {
@@ -557,13 +2467,16 @@ describe("simple_vault", () => {
     
     // New owner withdraws tokens
     await program.methods
-      .withdraw(withdrawAmount)
+      .withdraw(withdrawAmount, mintKeypair.publicKey)
       .accounts({
         vault: vaultPDA,
         vaultTokenAccount: vaultTokenAccount.publicKey,
+        tokenMint: mintKeypair.publicKey,
         userTokenAccount: newOwnerTokenAccount,
+        pendingTransaction: null,
         owner: newOwnerKeypair.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
       })
       .signers([newOwnerKeypair])
       .rpc();
@@ -587,13 +2500,16 @@ describe("simple_vault", () => {
   it("Former owner cannot withdraw from vault anymore", async () => {
     try {
       await program.methods
-        .withdraw(withdrawAmount)
+        .withdraw(withdrawAmount, mintKeypair.publicKey)
         .accounts({
           vault: vaultPDA,
           vaultTokenAccount: vaultTokenAccount.publicKey,
+          tokenMint: mintKeypair.publicKey,
           userTokenAccount: userTokenAccount,
+          pendingTransaction: null,
           owner: ownerKeypair.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
         .signers([ownerKeypair])
         .rpc();
@@ -610,7 +2526,9 @@ describe("simple_vault", () => {
       .initiateOwnershipTransfer(ownerKeypair.publicKey)
       .accounts({
         vault: vaultPDA,
+        pendingTransaction: null,
         owner: newOwnerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
       })
       .signers([newOwnerKeypair])
       .rpc();
@@ -630,6 +2548,7 @@ describe("simple_vault", () => {
       .cancelOwnershipTransfer()
       .accounts({
         vault: vaultPDA,
+        pendingTransaction: null,
         owner: newOwnerKeypair.publicKey,
       })
       .signers([newOwnerKeypair])
@@ -639,4 +2558,75 @@ describe("simple_vault", () => {
     const vaultAccount = await program.account.vault.fetch(vaultPDA);
     assert.equal(vaultAccount.transferOwnershipTo, null, "Pending transfer should be cleared");
   });
+
+  it("Owner can close the vault and reclaim rent", async () => {
+    const vaultBalanceBefore = await provider.connection.getTokenAccountBalance(vaultTokenAccount.publicKey);
+    const newOwnerBalanceBefore = await provider.connection.getTokenAccountBalance(newOwnerTokenAccount);
+    const settingAccounts = {
+      vault: vaultPDA,
+      pendingTransaction: null,
+      owner: newOwnerKeypair.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+    const closeVault = () =>
+      program.methods
+        .closeVault()
+        .accounts({
+          vault: vaultPDA,
+          vaultTokenAccount: vaultTokenAccount.publicKey,
+          tokenMint: mintKeypair.publicKey,
+          destinationTokenAccount: newOwnerTokenAccount,
+          pendingTransaction: null,
+          approvedTransaction: null,
+          owner: newOwnerKeypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([newOwnerKeypair])
+        .rpc();
+
+    // The sweep is bounded by the withdrawal limit like any other withdrawal
+    await program.methods
+      .setWithdrawalLimit(new anchor.BN("18446744073709551615"))
+      .accounts(settingAccounts)
+      .signers([newOwnerKeypair])
+      .rpc();
+
+    // A delayed vault cannot be swept in one step
+    await program.methods
+      .setWithdrawalDelay(new anchor.BN(3600))
+      .accounts(settingAccounts)
+      .signers([newOwnerKeypair])
+      .rpc();
+    try {
+      await closeVault();
+
+      assert.fail("Should have thrown an error due to the withdrawal delay");
+    } catch (error) {
+      assert(error.toString().includes("WithdrawalDelayRequired"), "Expected WithdrawalDelayRequired error");
+    }
+    await program.methods
+      .setWithdrawalDelay(new anchor.BN(0))
+      .accounts(settingAccounts)
+      .signers([newOwnerKeypair])
+      .rpc();
+
+    await closeVault();
+
+    // Remaining tokens are swept to the destination
+    const newOwnerBalance = await provider.connection.getTokenAccountBalance(newOwnerTokenAccount);
+    assert.equal(
+      Number(newOwnerBalance.value.amount) - Number(newOwnerBalanceBefore.value.amount),
+      Number(vaultBalanceBefore.value.amount),
+      "Remaining vault balance should be swept to the destination"
+    );
+
+    // Both accounts are closed
+    assert.equal(await provider.connection.getAccountInfo(vaultPDA), null, "Vault account should be closed");
+    assert.equal(
+      await provider.connection.getAccountInfo(vaultTokenAccount.publicKey),
+      null,
+      "Vault token account should be closed"
+    );
+  });
 });